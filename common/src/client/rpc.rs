@@ -219,6 +219,29 @@ pub fn get_account<T: Pack>(client: &RpcClient, addr: &Pubkey) -> Result<T> {
     T::unpack(&account.data).map_err(Into::into)
 }
 
+/// Fetches and unpacks the given accounts, batching requests with
+/// `getMultipleAccounts`. Results are in the same order as `addrs`, with
+/// `None` for accounts that don't exist.
+pub fn get_accounts<T: Pack>(client: &RpcClient, addrs: &[Pubkey]) -> Result<Vec<Option<T>>> {
+    // Max number of keys accepted by a single getMultipleAccounts request.
+    const MAX_KEYS: usize = 100;
+
+    let mut accounts = Vec::with_capacity(addrs.len());
+    for chunk in addrs.chunks(MAX_KEYS) {
+        let resp = client
+            .get_multiple_accounts_with_commitment(chunk, CommitmentConfig::recent())?
+            .value;
+        for account in resp {
+            let account = match account {
+                None => None,
+                Some(account) => Some(T::unpack(&account.data)?),
+            };
+            accounts.push(account);
+        }
+    }
+    Ok(accounts)
+}
+
 // Convenience for testing. Use `get_token_account` otherwise.
 pub fn account_token_unpacked<T: TokenPack>(client: &RpcClient, addr: &Pubkey) -> T {
     get_token_account::<T>(client, addr).unwrap()
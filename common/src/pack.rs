@@ -67,8 +67,32 @@ pub trait Pack: std::marker::Sized {
 /// When using this, one should consider the performance impact of using
 /// Serde and the associated serializer when targeting BPF. The state of this
 /// is not entirely clear as of now.
+///
+/// If given a `[u8; DISCRIMINATOR_LEN]` discriminator as a second argument,
+/// the serialization is prefixed with it and unpacking fails on any other
+/// prefix, so that one account type can't be passed off as another. An all
+/// zero prefix, i.e., a newly created account, is accepted as well.
 #[macro_export]
 macro_rules! packable {
+    ($my_struct:ty, $discriminator:expr) => {
+        impl Pack for $my_struct {
+            fn pack(src: $my_struct, dst: &mut [u8]) -> Result<(), ProgramError> {
+                if src.size()? != dst.len() as u64 {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                serum_common::pack::into_bytes_with_discriminator(&$discriminator, &src, dst)
+            }
+
+            fn unpack_unchecked(src: &mut &[u8]) -> Result<$my_struct, ProgramError> {
+                serum_common::pack::from_reader_with_discriminator(&$discriminator, src)
+            }
+
+            fn size(&self) -> Result<u64, ProgramError> {
+                serum_common::pack::bytes_size(&self)
+                    .map(|size| size + serum_common::pack::DISCRIMINATOR_LEN as u64)
+            }
+        }
+    };
     ($my_struct:ty) => {
         impl Pack for $my_struct {
             fn pack(src: $my_struct, dst: &mut [u8]) -> Result<(), ProgramError> {
@@ -89,6 +113,50 @@ macro_rules! packable {
     };
 }
 
+/// Implements `Pack` for a type implementing `Versioned`, with the given
+/// discriminator. The serialization is prefixed with the discriminator and
+/// the layout version.
+///
+/// Unlike `packable!`, the destination may be larger than the serialization,
+/// since versioned accounts are allocated with `RESERVED_LEN` spare bytes,
+/// and unpacking an account written with an older layout upgrades it.
+#[macro_export]
+macro_rules! versioned_packable {
+    ($my_struct:ty, $discriminator:expr) => {
+        impl Pack for $my_struct {
+            fn pack(src: $my_struct, dst: &mut [u8]) -> Result<(), ProgramError> {
+                if src.size()? > dst.len() as u64 {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                serum_common::pack::into_bytes_versioned(
+                    &$discriminator,
+                    <$my_struct as serum_common::pack::Versioned>::VERSION,
+                    &src,
+                    dst,
+                )
+            }
+
+            fn unpack_unchecked(src: &mut &[u8]) -> Result<$my_struct, ProgramError> {
+                serum_common::pack::from_reader_versioned(&$discriminator, src)
+            }
+
+            fn size(&self) -> Result<u64, ProgramError> {
+                serum_common::pack::bytes_size(&self).map(|size| {
+                    size + (serum_common::pack::DISCRIMINATOR_LEN + serum_common::pack::VERSION_LEN)
+                        as u64
+                })
+            }
+
+            // Accounts are allocated past their serialization, so trailing
+            // bytes are expected.
+            fn unpack(src: &[u8]) -> Result<$my_struct, ProgramError> {
+                let mut src_mut = src;
+                <$my_struct>::unpack_unchecked(&mut src_mut)
+            }
+        }
+    };
+}
+
 pub fn to_bytes<T: ?Sized>(i: &T) -> Result<Vec<u8>, ProgramError>
 where
     T: serde::Serialize,
@@ -119,6 +187,122 @@ where
     bincode::deserialize_from(rdr).map_err(|_| ProgramError::InvalidAccountData)
 }
 
+/// Length of the account type discriminator written by `packable!`.
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Same as `into_bytes`, prefixing the serialization with `discriminator`.
+pub fn into_bytes_with_discriminator<T: ?Sized>(
+    discriminator: &[u8; DISCRIMINATOR_LEN],
+    i: &T,
+    dst: &mut [u8],
+) -> Result<(), ProgramError>
+where
+    T: serde::Serialize,
+{
+    if dst.len() < DISCRIMINATOR_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (prefix, rest) = dst.split_at_mut(DISCRIMINATOR_LEN);
+    prefix.copy_from_slice(discriminator);
+    into_bytes(i, rest)
+}
+
+/// Same as `from_reader`, first consuming and checking the discriminator
+/// prefix. An all zero prefix, i.e., uninitialized data, is accepted.
+pub fn from_reader_with_discriminator<T>(
+    discriminator: &[u8; DISCRIMINATOR_LEN],
+    src: &mut &[u8],
+) -> Result<T, ProgramError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    if src.len() < DISCRIMINATOR_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (prefix, rest) = src.split_at(DISCRIMINATOR_LEN);
+    if prefix != discriminator && prefix != [0; DISCRIMINATOR_LEN] {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    *src = rest;
+    from_reader(src)
+}
+
+/// Length of the layout version following the discriminator of versioned
+/// accounts.
+pub const VERSION_LEN: usize = 1;
+
+/// Number of spare bytes versioned accounts are allocated with past their
+/// serialization. Accounts can't be resized, so this bounds how much later
+/// layouts can grow while still migrating existing accounts in place.
+pub const RESERVED_LEN: usize = 64;
+
+/// Account types with a versioned layout, serialized with
+/// `versioned_packable!`.
+pub trait Versioned: serde::de::DeserializeOwned {
+    /// Version of the current layout, written on every pack. Starts at 1,
+    /// since 0 marks uninitialized data.
+    const VERSION: u8;
+
+    /// Deserializes the rest of an account written with the older layout
+    /// `version`, upgrading it to the current layout.
+    fn upgrade(version: u8, src: &mut &[u8]) -> Result<Self, ProgramError>;
+}
+
+/// Same as `into_bytes_with_discriminator`, following the discriminator with
+/// the layout version.
+pub fn into_bytes_versioned<T: ?Sized>(
+    discriminator: &[u8; DISCRIMINATOR_LEN],
+    version: u8,
+    i: &T,
+    dst: &mut [u8],
+) -> Result<(), ProgramError>
+where
+    T: serde::Serialize,
+{
+    if dst.len() < DISCRIMINATOR_LEN + VERSION_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (prefix, rest) = dst.split_at_mut(DISCRIMINATOR_LEN);
+    prefix.copy_from_slice(discriminator);
+    let (prefix, rest) = rest.split_at_mut(VERSION_LEN);
+    prefix[0] = version;
+    into_bytes(i, rest)
+}
+
+/// Same as `from_reader_with_discriminator`, deserializing with the layout
+/// of the version following the discriminator. Layouts newer than the
+/// current one are rejected.
+pub fn from_reader_versioned<T: Versioned>(
+    discriminator: &[u8; DISCRIMINATOR_LEN],
+    src: &mut &[u8],
+) -> Result<T, ProgramError> {
+    let version = layout_version(src)?;
+    let uninitialized = src[..DISCRIMINATOR_LEN] == [0; DISCRIMINATOR_LEN];
+    if uninitialized && version != 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !uninitialized && (src[..DISCRIMINATOR_LEN] != *discriminator || version == 0) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    *src = &src[DISCRIMINATOR_LEN + VERSION_LEN..];
+    if uninitialized || version == T::VERSION {
+        from_reader(src)
+    } else if version < T::VERSION {
+        T::upgrade(version, src)
+    } else {
+        Err(ProgramError::InvalidAccountData)
+    }
+}
+
+/// Returns the layout version of a versioned account's data, without
+/// deserializing it. Zero if uninitialized.
+pub fn layout_version(data: &[u8]) -> Result<u8, ProgramError> {
+    if data.len() < DISCRIMINATOR_LEN + VERSION_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(data[DISCRIMINATOR_LEN])
+}
+
 pub fn bytes_size<T: ?Sized>(value: &T) -> Result<u64, ProgramError>
 where
     T: serde::Serialize,
@@ -197,4 +381,123 @@ mod tests {
         let r = VarLenStruct::unpack(&data);
         assert_eq!(r.unwrap_err(), ProgramError::InvalidAccountData);
     }
+
+    #[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct TaggedA {
+        a: u64,
+    }
+    packable!(TaggedA, *b"tagged:a");
+
+    #[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct TaggedB {
+        b: u64,
+    }
+    packable!(TaggedB, *b"tagged:b");
+
+    #[test]
+    fn discriminator_pack_unpack() {
+        let strct = TaggedA { a: 7 };
+        assert_eq!(strct.size().unwrap(), 16);
+        let mut dst = vec![0; 16];
+        TaggedA::pack(strct.clone(), &mut dst).unwrap();
+        assert_eq!(&dst[..DISCRIMINATOR_LEN], b"tagged:a");
+        assert_eq!(TaggedA::unpack(&dst).unwrap(), strct);
+    }
+
+    #[test]
+    fn discriminator_mismatch() {
+        let mut dst = vec![0; 16];
+        TaggedA::pack(TaggedA { a: 7 }, &mut dst).unwrap();
+        let r = TaggedB::unpack(&dst);
+        assert_eq!(r.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn discriminator_uninitialized() {
+        let data = vec![0; 16];
+        assert_eq!(TaggedB::unpack(&data).unwrap(), TaggedB::default());
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct VersionedV1 {
+        a: u64,
+    }
+    packable!(VersionedV1);
+
+    #[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct VersionedV2 {
+        a: u64,
+        b: u64,
+    }
+    versioned_packable!(VersionedV2, *b"version:");
+
+    impl super::Versioned for VersionedV2 {
+        const VERSION: u8 = 2;
+
+        fn upgrade(version: u8, src: &mut &[u8]) -> Result<Self, ProgramError> {
+            match version {
+                1 => {
+                    let v1: VersionedV1 = from_reader(src)?;
+                    Ok(VersionedV2 { a: v1.a, b: 0 })
+                }
+                _ => Err(ProgramError::InvalidAccountData),
+            }
+        }
+    }
+
+    const VERSIONED_SIZE: usize = DISCRIMINATOR_LEN + VERSION_LEN + 16 + RESERVED_LEN;
+
+    #[test]
+    fn versioned_pack_unpack() {
+        let strct = VersionedV2 { a: 1, b: 2 };
+        let mut dst = vec![0; VERSIONED_SIZE];
+        VersionedV2::pack(strct.clone(), &mut dst).unwrap();
+        assert_eq!(&dst[..DISCRIMINATOR_LEN], b"version:");
+        assert_eq!(layout_version(&dst).unwrap(), 2);
+        assert_eq!(VersionedV2::unpack(&dst).unwrap(), strct);
+        assert_eq!(
+            VersionedV2::unpack(&vec![0; VERSIONED_SIZE]).unwrap(),
+            VersionedV2::default()
+        );
+    }
+
+    #[test]
+    fn versioned_upgrade_in_place() {
+        // An account written with the first layout.
+        let mut data = vec![0; VERSIONED_SIZE];
+        data[..DISCRIMINATOR_LEN].copy_from_slice(b"version:");
+        data[DISCRIMINATOR_LEN] = 1;
+        VersionedV1::pack(
+            VersionedV1 { a: 7 },
+            &mut data[DISCRIMINATOR_LEN + VERSION_LEN..DISCRIMINATOR_LEN + VERSION_LEN + 8],
+        )
+        .unwrap();
+
+        VersionedV2::unpack_mut(&mut data, &mut |v: &mut VersionedV2| {
+            assert_eq!(*v, VersionedV2 { a: 7, b: 0 });
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(layout_version(&data).unwrap(), 2);
+        assert_eq!(
+            VersionedV2::unpack(&data).unwrap(),
+            VersionedV2 { a: 7, b: 0 }
+        );
+    }
+
+    #[test]
+    fn versioned_rejects_unknown_versions() {
+        let mut data = vec![0; VERSIONED_SIZE];
+        VersionedV2::pack(VersionedV2::default(), &mut data).unwrap();
+        for version in [0, 3].iter() {
+            data[DISCRIMINATOR_LEN] = *version;
+            let r = VersionedV2::unpack(&data);
+            assert_eq!(r.unwrap_err(), ProgramError::InvalidAccountData);
+        }
+        // Zeroed data must have a zero version.
+        let mut data = vec![0; VERSIONED_SIZE];
+        data[DISCRIMINATOR_LEN] = 2;
+        let r = VersionedV2::unpack(&data);
+        assert_eq!(r.unwrap_err(), ProgramError::InvalidAccountData);
+    }
 }
@@ -142,3 +142,32 @@ pub fn client_at<T: ClientGen>(program_id: Pubkey) -> T {
             },
         })
 }
+
+// Blocks until the cluster reaches the given slot, returning the slot it's
+// at. Tests run against a live validator, whose clock can't be warped, so
+// slot dependent behavior, e.g. timelocks, is tested by waiting it out.
+//
+// Panics if the slot isn't reached in time, allowing a second per slot,
+// more than twice the nominal slot time, so that a stalled validator fails
+// the test rather than hanging it.
+pub fn wait_for_slot<T: ClientGen>(client: &T, slot: u64) -> u64 {
+    let start = client.rpc().get_slot().unwrap();
+    let timeout = WAIT_FOR_SLOT_GRACE + std::time::Duration::from_secs(slot.saturating_sub(start));
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        let current = client.rpc().get_slot().unwrap();
+        if current >= slot {
+            return current;
+        }
+        if std::time::Instant::now() >= deadline {
+            panic!(
+                "timed out after {:?} waiting for slot {}, cluster is at {}",
+                timeout, slot, current
+            );
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
+
+// Time allowed on top of the per slot allowance of `wait_for_slot`.
+const WAIT_FOR_SLOT_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
@@ -66,6 +66,9 @@ pub enum SubCommand {
         /// Amount of tokens to give this Vesting account.
         #[clap(short = 'a', long)]
         deposit_amount: u64,
+        /// Slot before which nothing vests. Defaults to no cliff.
+        #[clap(long, default_value = "0")]
+        cliff_slot: u64,
     },
     /// Claim a vesting account, receiving a non-fungible token receipt.
     Claim {
@@ -182,6 +185,7 @@ pub fn run(opts: Opts) -> Result<()> {
             end_slot,
             period_count,
             deposit_amount,
+            cliff_slot,
         } => {
             let client = ctx.connect::<Client>(opts.cmd.pid)?;
             let resp = client.create_vesting(CreateVestingRequest {
@@ -192,6 +196,7 @@ pub fn run(opts: Opts) -> Result<()> {
                 end_slot,
                 period_count,
                 deposit_amount,
+                cliff_slot,
             })?;
             println!("{:#?}", resp);
             Ok(())
@@ -126,6 +126,7 @@ pub fn create_vesting_account(
     end_slot: u64,
     period_count: u64,
     deposit_amount: u64,
+    cliff_slot: u64,
     mint_decimals: u8,
 ) -> Result<(Signature, Keypair, Pubkey), InnerClientError> {
     let mint_kp = Keypair::generate(&mut OsRng);
@@ -173,6 +174,7 @@ pub fn create_vesting_account(
         end_slot,
         period_count,
         deposit_amount,
+        cliff_slot,
     );
 
     let instructions = [create_account_instr, create_vesting_instr];
@@ -49,6 +49,7 @@ impl Client {
             req.end_slot,
             req.period_count,
             req.deposit_amount,
+            req.cliff_slot,
             mint_decimals,
         )
         .map_err(Into::into)
@@ -381,6 +382,8 @@ pub struct CreateVestingRequest<'a> {
     pub end_slot: u64,
     pub period_count: u64,
     pub deposit_amount: u64,
+    /// Slot before which nothing vests, or zero for no cliff.
+    pub cliff_slot: u64,
 }
 
 #[derive(Debug)]
@@ -71,6 +71,15 @@ pub fn vesting(
     Ok(vesting)
 }
 
+/// Access control on any instruction invoking the SPL token program, so
+/// that transfers can't be routed through an impostor.
+pub fn token_program(acc_info: &AccountInfo) -> Result<(), LockupError> {
+    if *acc_info.key != spl_token::ID {
+        return Err(LockupErrorCode::InvalidTokenProgram)?;
+    }
+    Ok(())
+}
+
 pub fn rent(acc_info: &AccountInfo) -> Result<Rent, LockupError> {
     if *acc_info.key != solana_sdk::sysvar::rent::id() {
         return Err(LockupErrorCode::InvalidRentSysvar)?;
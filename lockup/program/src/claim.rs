@@ -19,12 +19,13 @@ pub fn handler<'a>(
     let vesting_acc_info = next_account_info(acc_infos)?;
     let safe_acc_info = next_account_info(acc_infos)?;
     let safe_vault_authority_acc_info = next_account_info(acc_infos)?;
-    let _token_prog_acc_info = next_account_info(acc_infos)?;
+    let token_program_acc_info = next_account_info(acc_infos)?;
     let mint_acc_info = next_account_info(acc_infos)?;
     let token_acc_info = next_account_info(acc_infos)?;
 
     access_control(AccessControlRequest {
         program_id,
+        token_program_acc_info,
         safe_acc_info,
         safe_vault_authority_acc_info,
         vesting_acc_info,
@@ -58,6 +59,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
 
     let AccessControlRequest {
         program_id,
+        token_program_acc_info,
         safe_acc_info,
         safe_vault_authority_acc_info,
         vesting_acc_info,
@@ -72,6 +74,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
     }
 
     // Account validation.
+    access_control::token_program(token_program_acc_info)?;
     let safe = access_control::safe(safe_acc_info, program_id)?;
     let _ = access_control::vault_authority(
         safe_vault_authority_acc_info,
@@ -145,6 +148,7 @@ fn state_transition<'a, 'b>(req: StateTransitionRequest<'a, 'b>) -> Result<(), L
 
 struct AccessControlRequest<'a> {
     program_id: &'a Pubkey,
+    token_program_acc_info: &'a AccountInfo<'a>,
     safe_acc_info: &'a AccountInfo<'a>,
     safe_vault_authority_acc_info: &'a AccountInfo<'a>,
     vesting_acc_info: &'a AccountInfo<'a>,
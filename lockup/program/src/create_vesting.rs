@@ -15,6 +15,7 @@ pub fn handler<'a>(
     end_slot: u64,
     period_count: u64,
     deposit_amount: u64,
+    cliff_slot: u64,
 ) -> Result<(), LockupError> {
     info!("handler: create_vesting");
 
@@ -34,9 +35,11 @@ pub fn handler<'a>(
 
     access_control(AccessControlRequest {
         program_id,
+        token_program_acc_info,
         end_slot,
         period_count,
         deposit_amount,
+        cliff_slot,
         vesting_acc_info,
         safe_acc_info,
         depositor_authority_acc_info,
@@ -55,6 +58,7 @@ pub fn handler<'a>(
                 end_slot,
                 period_count,
                 deposit_amount,
+                cliff_slot,
                 vesting_acc,
                 vesting_acc_beneficiary,
                 safe_acc_info,
@@ -76,9 +80,11 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
 
     let AccessControlRequest {
         program_id,
+        token_program_acc_info,
         end_slot,
         period_count,
         deposit_amount,
+        cliff_slot,
         vesting_acc_info,
         vault_authority_acc_info,
         safe_acc_info,
@@ -95,6 +101,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
     }
 
     // Account validation.
+    access_control::token_program(token_program_acc_info)?;
     let rent = access_control::rent(rent_acc_info)?;
     let safe = access_control::safe(safe_acc_info, program_id)?;
     let _ = access_control::vault(
@@ -125,7 +132,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
         }
         // Vesting schedule.
         {
-            if end_slot <= clock_slot {
+            if end_slot <= clock_slot || cliff_slot > end_slot {
                 return Err(LockupErrorCode::InvalidSlot)?;
             }
             if period_count == 0 {
@@ -165,6 +172,7 @@ fn state_transition<'a, 'b>(req: StateTransitionRequest<'a, 'b>) -> Result<(), L
         end_slot,
         period_count,
         deposit_amount,
+        cliff_slot,
         vesting_acc,
         vesting_acc_beneficiary,
         safe_acc_info,
@@ -188,6 +196,7 @@ fn state_transition<'a, 'b>(req: StateTransitionRequest<'a, 'b>) -> Result<(), L
         vesting_acc.balance = deposit_amount;
         vesting_acc.locked_nft_mint = *nft_mint_acc_info.key;
         vesting_acc.whitelist_owned = 0;
+        vesting_acc.cliff_slot = cliff_slot;
     }
 
     // Now transfer SPL funds from the depositor, to the
@@ -221,9 +230,11 @@ fn state_transition<'a, 'b>(req: StateTransitionRequest<'a, 'b>) -> Result<(), L
 
 struct AccessControlRequest<'a> {
     program_id: &'a Pubkey,
+    token_program_acc_info: &'a AccountInfo<'a>,
     end_slot: u64,
     period_count: u64,
     deposit_amount: u64,
+    cliff_slot: u64,
     vesting_acc_info: &'a AccountInfo<'a>,
     safe_acc_info: &'a AccountInfo<'a>,
     depositor_authority_acc_info: &'a AccountInfo<'a>,
@@ -239,6 +250,7 @@ struct StateTransitionRequest<'a, 'b> {
     end_slot: u64,
     period_count: u64,
     deposit_amount: u64,
+    cliff_slot: u64,
     vesting_acc: &'b mut Vesting,
     vesting_acc_beneficiary: Pubkey,
     safe_acc_info: &'a AccountInfo<'a>,
@@ -42,6 +42,7 @@ fn entry<'a>(
             end_slot,
             period_count,
             deposit_amount,
+            cliff_slot,
         } => create_vesting::handler(
             program_id,
             accounts,
@@ -49,6 +50,7 @@ fn entry<'a>(
             end_slot,
             period_count,
             deposit_amount,
+            cliff_slot,
         ),
         LockupInstruction::Claim => claim::handler(program_id, accounts),
         LockupInstruction::Redeem { amount } => redeem::handler(program_id, accounts, amount),
@@ -25,6 +25,7 @@ pub fn handler<'a>(
 
     access_control(AccessControlRequest {
         program_id,
+        token_program_acc_info,
         safe_acc_info,
         safe_authority_acc_info,
     })?;
@@ -55,6 +56,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
 
     let AccessControlRequest {
         program_id,
+        token_program_acc_info,
         safe_acc_info,
         safe_authority_acc_info,
     } = req;
@@ -62,6 +64,9 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
     // Governance authorization.
     let _ = access_control::governance(program_id, safe_acc_info, safe_authority_acc_info)?;
 
+    // Account validation.
+    access_control::token_program(token_program_acc_info)?;
+
     info!("access-control: success");
 
     Ok(())
@@ -110,6 +115,7 @@ fn state_transition<'a, 'b>(req: StateTransitionRequest<'a, 'b>) -> Result<(), L
 
 struct AccessControlRequest<'a> {
     program_id: &'a Pubkey,
+    token_program_acc_info: &'a AccountInfo<'a>,
     safe_acc_info: &'a AccountInfo<'a>,
     safe_authority_acc_info: &'a AccountInfo<'a>,
 }
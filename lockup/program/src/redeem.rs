@@ -29,6 +29,7 @@ pub fn handler<'a>(
 
     access_control(AccessControlRequest {
         program_id,
+        token_program_acc_info,
         amount,
         vesting_acc_beneficiary_info,
         vesting_acc_info,
@@ -66,6 +67,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
 
     let AccessControlRequest {
         program_id,
+        token_program_acc_info,
         amount,
         vesting_acc_beneficiary_info,
         vesting_acc_info,
@@ -83,6 +85,7 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), LockupError>
     }
 
     // Account validation.
+    access_control::token_program(token_program_acc_info)?;
     let _ = access_control::safe(safe_acc_info, program_id)?;
     let _ = access_control::vault(
         safe_vault_acc_info,
@@ -189,6 +192,7 @@ fn state_transition<'a, 'b>(req: StateTransitionRequest<'a, 'b>) -> Result<(), L
 
 struct AccessControlRequest<'a> {
     program_id: &'a Pubkey,
+    token_program_acc_info: &'a AccountInfo<'a>,
     amount: u64,
     vesting_acc_beneficiary_info: &'a AccountInfo<'a>,
     vesting_acc_info: &'a AccountInfo<'a>,
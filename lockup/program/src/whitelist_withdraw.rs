@@ -37,6 +37,7 @@ pub fn handler<'a>(
 
     access_control(AccessControlRequest {
         program_id,
+        tok_prog_acc_info,
         beneficiary_acc_info,
         vesting_acc_info,
         wl_acc_info,
@@ -79,6 +80,7 @@ fn access_control(req: AccessControlRequest) -> Result<(), LockupError> {
 
     let AccessControlRequest {
         program_id,
+        tok_prog_acc_info,
         beneficiary_acc_info,
         vesting_acc_info,
         wl_acc_info,
@@ -96,6 +98,7 @@ fn access_control(req: AccessControlRequest) -> Result<(), LockupError> {
     }
 
     // Account validation.
+    access_control::token_program(tok_prog_acc_info)?;
     let safe = access_control::safe(safe_acc_info, program_id)?;
     let whitelist = access_control::whitelist(wl_acc_info.clone(), &safe, program_id)?;
     let _ = access_control::vault(
@@ -215,6 +218,7 @@ fn state_transition(req: StateTransitionRequest) -> Result<(), LockupError> {
 
 struct AccessControlRequest<'a> {
     program_id: &'a Pubkey,
+    tok_prog_acc_info: &'a AccountInfo<'a>,
     beneficiary_acc_info: &'a AccountInfo<'a>,
     vesting_acc_info: &'a AccountInfo<'a>,
     safe_acc_info: &'a AccountInfo<'a>,
@@ -42,6 +42,10 @@ pub struct Vesting {
     pub locked_nft_token: Pubkey,
     /// The amount of tokens in custody of whitelisted programs.
     pub whitelist_owned: u64,
+    /// The slot before which nothing vests. At the cliff, everything the
+    /// schedule would have vested by then unlocks at once. Zero, or any slot
+    /// before the first period ends, for no cliff.
+    pub cliff_slot: u64,
 }
 
 impl Vesting {
@@ -63,6 +67,31 @@ impl Vesting {
         )
     }
 
+    /// Projects the vesting schedule, returning each slot at which tokens
+    /// unlock along with the total amount vested as of that slot. The last
+    /// item is always the `end_slot`, at which the `start_balance` is vested.
+    pub fn unlock_schedule(&self) -> Vec<(u64, u64)> {
+        let mut schedule: Vec<(u64, u64)> = vec![];
+        let (start_slot, period) = self.vesting_window();
+        if period != 0 {
+            for k in 1..=self.period_count {
+                // Periods ending before the cliff unlock at the cliff.
+                let slot = std::cmp::max(start_slot + k * period, self.start_slot);
+                let slot = std::cmp::max(slot, self.cliff_slot);
+                if slot >= self.end_slot {
+                    break;
+                }
+                let vested = self.total_vested(slot);
+                match schedule.last_mut() {
+                    Some(last) if last.0 == slot => last.1 = vested,
+                    _ => schedule.push((slot, vested)),
+                }
+            }
+        }
+        schedule.push((self.end_slot, self.start_balance));
+        schedule
+    }
+
     /// Amount available for whitelisted programs to transfer.
     pub fn available_for_whitelist(&self) -> u64 {
         self.balance - self.whitelist_owned
@@ -82,6 +111,9 @@ impl Vesting {
         if current_slot >= self.end_slot {
             return self.start_balance;
         }
+        if current_slot < self.cliff_slot {
+            return 0;
+        }
         self.linear_unlock(current_slot)
     }
 
@@ -90,24 +122,25 @@ impl Vesting {
         self.start_balance - self.balance
     }
 
+    // Returns the slot the vesting window starts at and the length of each
+    // period.
+    fn vesting_window(&self) -> (u64, u64) {
+        // If we can't perfectly partition the vesting window,
+        // push the start window back so that we can.
+        //
+        // This has the effect of making the first vesting period act as
+        // a minor "cliff" that vests slightly more than the rest of the
+        // periods.
+        let overflow = (self.end_slot - self.start_slot) % self.period_count;
+        let start_slot = self.start_slot - overflow;
+        let period = (self.end_slot - start_slot) / self.period_count;
+        (start_slot, period)
+    }
+
     fn linear_unlock(&self, current_slot: u64) -> u64 {
-        let (end_slot, start_slot) = {
-            // If we can't perfectly partition the vesting window,
-            // push the start window back so that we can.
-            //
-            // This has the effect of making the first vesting period act as
-            // a minor "cliff" that vests slightly more than the rest of the
-            // periods.
-            let overflow = (self.end_slot - self.start_slot) % self.period_count;
-            if overflow != 0 {
-                (self.end_slot, self.start_slot - overflow)
-            } else {
-                (self.end_slot, self.start_slot)
-            }
-        };
+        let (start_slot, period) = self.vesting_window();
 
         let vested_period_count = {
-            let period = (end_slot - start_slot) / self.period_count;
             let current_period_count = (current_slot - start_slot) / period;
             std::cmp::min(current_period_count, self.period_count)
         };
@@ -140,6 +173,7 @@ mod tests {
         let whitelist_owned = 14;
         let claimed = true;
         let locked_nft_token = Pubkey::new_rand();
+        let cliff_slot = 15;
         let vesting_acc = Vesting {
             safe,
             claimed,
@@ -153,6 +187,7 @@ mod tests {
             locked_nft_mint,
             whitelist_owned,
             locked_nft_token,
+            cliff_slot,
         };
 
         // When I pack it into a slice.
@@ -173,6 +208,7 @@ mod tests {
         assert_eq!(va.locked_nft_mint, locked_nft_mint);
         assert_eq!(va.whitelist_owned, whitelist_owned);
         assert_eq!(va.locked_nft_token, locked_nft_token);
+        assert_eq!(va.cliff_slot, cliff_slot);
     }
 
     #[test]
@@ -202,6 +238,7 @@ mod tests {
             end_slot,
             period_count,
             locked_nft_token,
+            cliff_slot: 0,
         };
         assert_eq!(0, vesting_acc.available_for_withdrawal(10));
         assert_eq!(0, vesting_acc.available_for_withdrawal(11));
@@ -213,6 +250,61 @@ mod tests {
         assert_eq!(10, vesting_acc.available_for_withdrawal(100));
     }
 
+    #[test]
+    fn unlock_schedule() {
+        let mut vesting_acc = Vesting {
+            balance: 10,
+            start_balance: 10,
+            start_slot: 10,
+            end_slot: 20,
+            period_count: 5,
+            ..Default::default()
+        };
+        let schedule = vesting_acc.unlock_schedule();
+        assert_eq!(schedule, vec![(12, 2), (14, 4), (16, 6), (18, 8), (20, 10)]);
+        for (slot, vested) in schedule {
+            assert_eq!(vested, vesting_acc.available_for_withdrawal(slot));
+        }
+
+        // The window doesn't partition into periods evenly.
+        vesting_acc.period_count = 3;
+        let schedule = vesting_acc.unlock_schedule();
+        assert_eq!(schedule, vec![(12, 3), (15, 6), (18, 9), (20, 10)]);
+        for (slot, vested) in schedule {
+            assert_eq!(vested, vesting_acc.available_for_withdrawal(slot));
+        }
+    }
+
+    #[test]
+    fn cliff() {
+        let mut vesting_acc = Vesting {
+            balance: 10,
+            start_balance: 10,
+            start_slot: 10,
+            end_slot: 20,
+            period_count: 5,
+            cliff_slot: 15,
+            ..Default::default()
+        };
+        assert_eq!(0, vesting_acc.available_for_withdrawal(12));
+        assert_eq!(0, vesting_acc.available_for_withdrawal(14));
+        assert_eq!(4, vesting_acc.available_for_withdrawal(15));
+        assert_eq!(6, vesting_acc.available_for_withdrawal(16));
+        assert_eq!(10, vesting_acc.available_for_withdrawal(20));
+
+        // Periods ending before the cliff unlock together at the cliff.
+        let schedule = vesting_acc.unlock_schedule();
+        assert_eq!(schedule, vec![(15, 4), (16, 6), (18, 8), (20, 10)]);
+        for (slot, vested) in schedule {
+            assert_eq!(vested, vesting_acc.available_for_withdrawal(slot));
+        }
+
+        // A cliff at the end vests everything at once.
+        vesting_acc.cliff_slot = 20;
+        assert_eq!(0, vesting_acc.available_for_withdrawal(19));
+        assert_eq!(vesting_acc.unlock_schedule(), vec![(20, 10)]);
+    }
+
     #[test]
     fn unpack_zeroes() {
         let og_size = Vesting::default().size().unwrap();
@@ -86,11 +86,10 @@ impl<'a> Whitelist<'a> {
     }
 
     fn index_of(&self, e: &WhitelistEntry) -> Result<Option<usize>, LockupError> {
-        for k in (0..Whitelist::SIZE).step_by(Whitelist::ITEM_SIZE) {
-            let curr_idx = k / Whitelist::ITEM_SIZE;
-            let entry = &self.get_at(curr_idx)?;
+        for idx in 0..Whitelist::LEN {
+            let entry = &self.get_at(idx)?;
             if entry == e {
-                return Ok(Some(k));
+                return Ok(Some(idx));
             }
         }
         Ok(None)
@@ -163,3 +162,52 @@ impl WhitelistEntry {
 }
 
 serum_common::packable!(WhitelistEntry);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client_gen::solana_sdk::clock::Epoch;
+
+    fn entry(b: u8) -> WhitelistEntry {
+        WhitelistEntry::new(
+            Pubkey::new_from_array([b; 32]),
+            Pubkey::new_from_array([b; 32]),
+            b,
+        )
+    }
+
+    #[test]
+    fn push_and_delete() {
+        let key = Pubkey::new_rand();
+        let owner = Pubkey::new_rand();
+        let mut lamports = 0;
+        let mut data = vec![0; Whitelist::SIZE];
+        let acc_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            Epoch::default(),
+        );
+        let wl = Whitelist::new(acc_info).unwrap();
+
+        for k in 0..Whitelist::LEN {
+            let e = entry(k as u8 + 1);
+            assert_eq!(wl.push(e.clone()).unwrap(), Some(k));
+            assert_eq!(wl.get_at(k).unwrap(), e);
+        }
+
+        // Duplicates are rejected, before checking capacity.
+        assert!(wl.push(entry(1)).is_err());
+        assert_eq!(wl.push(entry(255)).unwrap(), None);
+
+        // Deleted slots are reused.
+        assert_eq!(wl.delete(entry(3)).unwrap(), Some(2));
+        assert_eq!(wl.get_at(2).unwrap(), WhitelistEntry::zero());
+        assert_eq!(wl.delete(entry(3)).unwrap(), None);
+        assert_eq!(wl.push(entry(255)).unwrap(), Some(2));
+    }
+}
@@ -66,6 +66,9 @@ pub mod instruction {
             period_count: u64,
             /// The amount to deposit into the vesting account.
             deposit_amount: u64,
+            /// The slot before which nothing vests, or zero for no cliff.
+            /// Can't be past `end_slot`.
+            cliff_slot: u64,
         },
         /// Claim is an instruction for one time use by the beneficiary of a
         /// Vesting account. It mints a non-fungible SPL token and sends it
@@ -79,6 +79,7 @@ pub fn deposit_with_schedule(deposit_amount: u64, end_slot: u64, period_count: u
                 end_slot,
                 period_count,
                 deposit_amount,
+                cliff_slot: 0,
             })
             .unwrap();
 
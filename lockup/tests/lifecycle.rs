@@ -55,6 +55,7 @@ fn lifecycle() {
                 end_slot,
                 period_count,
                 deposit_amount,
+                cliff_slot: 0,
             })
             .unwrap();
 
@@ -67,6 +68,7 @@ fn lifecycle() {
         assert_eq!(vesting_acc.initialized, true);
         assert_eq!(vesting_acc.end_slot, end_slot);
         assert_eq!(vesting_acc.period_count, period_count);
+        assert_eq!(vesting_acc.cliff_slot, 0);
         assert_eq!(vesting_acc.locked_nft_mint, mint);
         assert_eq!(vesting_acc.whitelist_owned, 0);
         // Then.
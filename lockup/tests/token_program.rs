@@ -0,0 +1,66 @@
+use common::lifecycle;
+use rand::rngs::OsRng;
+use serum_lockup::accounts::TokenVault;
+use serum_lockup::client::ClientError as InnerClientError;
+use serum_lockup::error::LockupErrorCode;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+
+mod common;
+
+#[test]
+fn invalid_token_program() {
+    // Given.
+    //
+    // An initialized safe with a deposit.
+    let lifecycle::Deposited {
+        client,
+        safe_acc,
+        srm_mint,
+        safe_authority,
+        ..
+    } = lifecycle::deposit_with_schedule(100, 100_000, 1);
+    let receiver_token_acc = serum_common::client::rpc::create_token_account(
+        client.rpc(),
+        &srm_mint.pubkey(),
+        &Keypair::generate(&mut OsRng).pubkey(),
+        client.payer(),
+    )
+    .unwrap();
+
+    // When.
+    //
+    // I migrate the safe, passing an impostor token program.
+    let safe = client.safe(&safe_acc).unwrap();
+    let vault_authority = Pubkey::create_program_address(
+        &TokenVault::signer_seeds(&safe_acc, &safe.nonce),
+        client.program(),
+    )
+    .unwrap();
+    let impostor = Pubkey::new_rand();
+    let accounts = [
+        AccountMeta::new_readonly(safe_authority.pubkey(), true),
+        AccountMeta::new(safe_acc, false),
+        AccountMeta::new(safe.vault, false),
+        AccountMeta::new_readonly(vault_authority, false),
+        AccountMeta::new(receiver_token_acc.pubkey(), false),
+        AccountMeta::new_readonly(impostor, false),
+    ];
+    let instr = serum_lockup::instruction::migrate(*client.program(), &accounts);
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instr],
+        Some(&client.payer().pubkey()),
+        &[client.payer(), &safe_authority],
+        recent_hash,
+    );
+    let err = client.rpc().send_and_confirm_transaction(&tx).unwrap_err();
+
+    // Then.
+    //
+    // The transaction is rejected and the vault untouched.
+    let code: u32 = LockupErrorCode::InvalidTokenProgram.into();
+    assert_eq!(InnerClientError::RpcError(err).error_code(), Some(code));
+    assert_eq!(client.vault(&safe_acc).unwrap().amount, 100);
+}
@@ -38,15 +38,38 @@ pub enum SubCommand {
         /// The keypair filepath for the node leader.
         #[clap(short, long)]
         leader: String,
+        /// Registrar to create the entity with.
+        #[clap(short, long)]
+        registrar: Pubkey,
         /// Flag for specifiying the crank capability. Required.
         #[clap(short, long)]
         crank: bool,
     },
+    /// Updates the leader and capabilities of a node entity.
+    UpdateEntity {
+        /// The keypair filepath for the current node leader.
+        #[clap(short, long)]
+        leader: String,
+        /// Node entity to update.
+        #[clap(short, long)]
+        entity: Pubkey,
+        /// Leader to hand the entity to [optional]. Defaults to the current
+        /// leader.
+        #[clap(short, long)]
+        new_leader: Option<Pubkey>,
+        /// Capabilities bitmap [optional]. Defaults to the current
+        /// capabilities.
+        #[clap(short, long)]
+        capabilities: Option<u64>,
+    },
     /// Joins an entity, creating an associated member account.
     JoinEntity {
         /// Node entity to join with.
         #[clap(short, long)]
         entity: Pubkey,
+        /// Registrar the entity was created with.
+        #[clap(short, long)]
+        registrar: Pubkey,
         /// Beneficiary of the stake account being created.
         #[clap(short, long)]
         beneficiary: Pubkey,
@@ -98,6 +121,9 @@ pub enum GovCommand {
         /// The amount of slots one must wait for a staking withdrawal.
         #[clap(short, long, default_value = "10000")]
         withdrawal_timelock: u64,
+        /// Forbids the authority from also leading an entity.
+        #[clap(long)]
+        separate_leader_and_authority: bool,
     },
     /// Registers a new node capability in the registrar.
     RegisterCapability {
@@ -123,14 +149,23 @@ pub fn run(opts: Opts) -> Result<()> {
     match opts.cmd.sub_cmd {
         SubCommand::Accounts(cmd) => account_cmd(ctx, registry_pid, cmd),
         SubCommand::Gov(cmd) => gov_cmd(ctx, registry_pid, cmd),
-        SubCommand::CreateEntity { crank, leader } => {
-            create_entity_cmd(ctx, registry_pid, leader, crank)
-        }
+        SubCommand::CreateEntity {
+            crank,
+            leader,
+            registrar,
+        } => create_entity_cmd(ctx, registry_pid, leader, registrar, crank),
+        SubCommand::UpdateEntity {
+            leader,
+            entity,
+            new_leader,
+            capabilities,
+        } => update_entity_cmd(ctx, registry_pid, leader, entity, new_leader, capabilities),
         SubCommand::JoinEntity {
             entity,
+            registrar,
             beneficiary,
             delegate,
-        } => join_entity_cmd(ctx, registry_pid, entity, beneficiary, delegate),
+        } => join_entity_cmd(ctx, registry_pid, entity, registrar, beneficiary, delegate),
     }
 }
 
@@ -138,6 +173,7 @@ fn join_entity_cmd(
     ctx: &Context,
     registry_pid: Option<Pubkey>,
     entity: Pubkey,
+    registrar: Pubkey,
     beneficiary: Pubkey,
     delegate: Option<Pubkey>,
 ) -> Result<()> {
@@ -146,7 +182,8 @@ fn join_entity_cmd(
 
     let client = ctx.connect::<Client>(registry_pid)?;
 
-    let (tx_sig, stake_addr) = client.join_entity_derived(entity, beneficiary, delegate)?;
+    let (tx_sig, stake_addr) =
+        client.join_entity_derived(entity, registrar, beneficiary, delegate)?;
 
     let logger = serum_node_logging::get_logger("node/registry");
     info!(logger, "Confirmed transaction: {:?}", tx_sig);
@@ -159,6 +196,7 @@ fn create_entity_cmd(
     ctx: &Context,
     registry_pid: Option<Pubkey>,
     leader_filepath: String,
+    registrar: Pubkey,
     crank: bool,
 ) -> Result<()> {
     let registry_pid = registry_pid.ok_or(anyhow!("--pid not provided"))?;
@@ -175,6 +213,7 @@ fn create_entity_cmd(
     let client = ctx.connect::<Client>(registry_pid)?;
     let (tx_sig, entity_address) = client.create_entity_derived(
         &leader_kp,
+        registrar,
         capabilities,
         serum_registry::accounts::StakeKind::Delegated,
     )?;
@@ -186,6 +225,43 @@ fn create_entity_cmd(
     Ok(())
 }
 
+fn update_entity_cmd(
+    ctx: &Context,
+    registry_pid: Option<Pubkey>,
+    leader_filepath: String,
+    entity: Pubkey,
+    new_leader: Option<Pubkey>,
+    capabilities: Option<u64>,
+) -> Result<()> {
+    let registry_pid = registry_pid.ok_or(anyhow!("--pid not provided"))?;
+
+    let leader_kp = solana_sdk::signature::read_keypair_file(&leader_filepath)
+        .map_err(|_| anyhow!("Unable to read leader keypair file"))?;
+
+    let client = ctx.connect::<Client>(registry_pid)?;
+    let entity_acc: Entity = rpc::get_account(client.rpc(), &entity)?;
+    let new_leader = new_leader.unwrap_or(entity_acc.leader);
+    let capabilities = capabilities.unwrap_or(entity_acc.capabilities);
+
+    let mut accounts = vec![
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader_kp.pubkey(), true),
+        AccountMeta::new_readonly(entity_acc.registrar, false),
+    ];
+    accounts.extend(client.capability_registry_account(&entity_acc.registrar)?);
+    let tx_sig = client.update_entity_with_signers(
+        &[&leader_kp, client.payer()],
+        &accounts,
+        new_leader,
+        capabilities,
+    )?;
+
+    let logger = serum_node_logging::get_logger("node/registry");
+    info!(logger, "Confirmed transaction: {:?}", tx_sig);
+
+    Ok(())
+}
+
 pub fn gov_cmd(ctx: &Context, registry_pid: Option<Pubkey>, gov_cmd: GovCommand) -> Result<()> {
     let registry_pid = registry_pid.ok_or(anyhow!("--pid not provided"))?;
     match gov_cmd {
@@ -193,12 +269,14 @@ pub fn gov_cmd(ctx: &Context, registry_pid: Option<Pubkey>, gov_cmd: GovCommand)
             authority,
             authority_file,
             withdrawal_timelock,
+            separate_leader_and_authority,
         } => gov::init(
             ctx,
             registry_pid,
             authority,
             authority_file,
             withdrawal_timelock,
+            separate_leader_and_authority,
         ),
         GovCommand::RegisterCapability {
             force_id,
@@ -233,8 +311,7 @@ fn account_cmd(ctx: &Context, registry_pid: Option<Pubkey>, cmd: AccountsCommand
                         "Please provide --pid when looking up entities by node leader"
                     ))?;
                     let leader = leader.expect("address or leader must be present");
-                    let seed = "srm:registry:entity";
-                    Pubkey::create_with_seed(&leader, &seed, &registry_pid)?
+                    serum_registry::addresses::entity(&leader, &registry_pid)?
                 }
             };
 
@@ -247,12 +324,8 @@ fn account_cmd(ctx: &Context, registry_pid: Option<Pubkey>, cmd: AccountsCommand
                 Some(a) => a,
                 None => {
                     let registry_pid = registry_pid.ok_or(anyhow!("--pid not provided"))?;
-                    Pubkey::create_with_seed(
-                        &ctx.wallet()?.pubkey(),
-                        Client::member_seed(),
-                        &registry_pid,
-                    )
-                    .map_err(|e| anyhow!("unable to derive stake address: {}", e.to_string()))?
+                    serum_registry::addresses::member(&ctx.wallet()?.pubkey(), &registry_pid)
+                        .map_err(|e| anyhow!("unable to derive stake address: {}", e.to_string()))?
                 }
             };
             let acc: Member = rpc::get_account(&rpc_client, &address)?;
@@ -271,6 +344,7 @@ mod gov {
         authority: Option<Pubkey>,
         authority_file: Option<String>,
         withdrawal_timelock: u64,
+        separate_leader_and_authority: bool,
     ) -> Result<()> {
         let logger = serum_node_logging::get_logger("node/registry");
 
@@ -289,8 +363,12 @@ mod gov {
             solana_sdk::sysvar::rent::ID,
             false,
         )];
-        let (_tx_sig, registrar_kp) =
-            client.create_account_and_initialize(&accounts, authority, withdrawal_timelock)?;
+        let (_tx_sig, registrar_kp) = client.create_account_and_initialize(
+            &accounts,
+            authority,
+            withdrawal_timelock,
+            separate_leader_and_authority,
+        )?;
 
         info!(
             logger,
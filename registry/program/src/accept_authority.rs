@@ -0,0 +1,87 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Registrar;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: accept_authority");
+
+    let acc_infos = &mut accounts.iter();
+
+    let pending_authority_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        pending_authority_acc_info,
+        registrar_acc_info,
+    })?;
+
+    Registrar::unpack_mut(
+        &mut registrar_acc_info.try_borrow_mut_data()?,
+        &mut |registrar: &mut Registrar| {
+            state_transition(StateTransitionRequest { registrar }).map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::AuthorityAccepted {
+        registrar: *registrar_acc_info.key,
+        authority: *pending_authority_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: accept_authority");
+
+    let AccessControlRequest {
+        program_id,
+        pending_authority_acc_info,
+        registrar_acc_info,
+    } = req;
+
+    if !pending_authority_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.pending_authority == Pubkey::default()
+        || registrar.pending_authority != *pending_authority_acc_info.key
+    {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: accept_authority");
+
+    let StateTransitionRequest { registrar } = req;
+
+    registrar.authority = registrar.pending_authority;
+    registrar.pending_authority = Pubkey::default();
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    pending_authority_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    registrar: &'a mut Registrar,
+}
@@ -0,0 +1,757 @@
+//! Module for safe access to accounts.
+
+use serum_common::pack::Pack;
+use serum_registry::accounts::{
+    CapabilityRegistry, Entity, EntityMetadata, Member, Multisig, Registrar,
+};
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sysvar::clock::Clock;
+use solana_sdk::sysvar::Sysvar;
+
+pub fn governance(
+    program_id: &Pubkey,
+    registrar_acc_info: &AccountInfo,
+    registrar_authority_acc_info: &AccountInfo,
+) -> Result<Registrar, RegistryError> {
+    if !registrar_authority_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    let registrar = registrar(registrar_acc_info, program_id)?;
+    if registrar.authority != *registrar_authority_acc_info.key {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    Ok(registrar)
+}
+
+pub fn registrar(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<Registrar, RegistryError> {
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    let registrar = Registrar::unpack(&acc_info.try_borrow_data()?)?;
+    if !registrar.initialized {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+
+    Ok(registrar)
+}
+
+/// Access control on any instruction requiring the entity leader's signature.
+/// The leader may also be a Multisig account, in which case at least `m` of
+/// its signers must be among `signer_acc_infos`.
+pub fn entity_leader_multisig(
+    program_id: &Pubkey,
+    entity_acc_info: &AccountInfo,
+    entity_leader_acc_info: &AccountInfo,
+    signer_acc_infos: &[AccountInfo],
+) -> Result<Entity, RegistryError> {
+    let entity = entity(entity_acc_info, program_id)?;
+    if entity.leader != *entity_leader_acc_info.key {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    if entity_leader_acc_info.is_signer {
+        return Ok(entity);
+    }
+    // A single key leader that didn't sign.
+    if entity_leader_acc_info.owner != program_id {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+
+    let multisig = multisig(entity_leader_acc_info, program_id)?;
+    let signed: Vec<&Pubkey> = signer_acc_infos
+        .iter()
+        .filter(|acc_info| acc_info.is_signer)
+        .map(|acc_info| acc_info.key)
+        .collect();
+    if !multisig.is_approved(&signed) {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+
+    Ok(entity)
+}
+
+pub fn entity(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<Entity, RegistryError> {
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    let entity = Entity::unpack(&acc_info.try_borrow_data()?)?;
+    if !entity.initialized {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+
+    Ok(entity)
+}
+
+pub fn member(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<Member, RegistryError> {
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    let member = Member::unpack(&acc_info.try_borrow_data()?)?;
+    if !member.initialized {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+
+    Ok(member)
+}
+
+pub fn entity_metadata(
+    acc_info: &AccountInfo,
+    entity_acc_info: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<EntityMetadata, RegistryError> {
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    let metadata = EntityMetadata::unpack(&acc_info.try_borrow_data()?)?;
+    if !metadata.initialized {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+    if metadata.entity != *entity_acc_info.key {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+
+    Ok(metadata)
+}
+
+pub fn capability_registry(
+    acc_info: &AccountInfo,
+    registrar_acc_info: &AccountInfo,
+    registrar: &Registrar,
+    program_id: &Pubkey,
+) -> Result<CapabilityRegistry, RegistryError> {
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    let capability_registry = CapabilityRegistry::unpack(&acc_info.try_borrow_data()?)?;
+    if !capability_registry.initialized {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+    if capability_registry.registrar != *registrar_acc_info.key
+        || registrar.capability_registry != *acc_info.key
+    {
+        return Err(RegistryErrorCode::InvalidCapabilityRegistry)?;
+    }
+
+    Ok(capability_registry)
+}
+
+/// Returns the next account if the registrar has a CapabilityRegistry.
+/// Instructions setting capabilities take the registry right after their
+/// fixed accounts, but only once the registrar has one.
+pub fn next_capability_registry<'a, 'b>(
+    registrar_acc_info: &AccountInfo,
+    acc_infos: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+) -> Result<Option<&'a AccountInfo<'b>>, RegistryError> {
+    // An invalid registrar is rejected by the instruction's access control.
+    let has_registry = Registrar::unpack(&registrar_acc_info.try_borrow_data()?)
+        .map(|r| r.capability_registry != Pubkey::default())
+        .unwrap_or(false);
+    if !has_registry {
+        return Ok(None);
+    }
+    Ok(Some(next_account_info(acc_infos)?))
+}
+
+/// Access control on any instruction setting capability bits on an entity.
+/// Every bit must be a registered capability: an entry in the registrar's
+/// CapabilityRegistry if it has one, otherwise a slot in its fee table. The
+/// fee table can't tell a zero fee capability from an empty slot, so there
+/// any slot is accepted.
+pub fn capabilities(
+    program_id: &Pubkey,
+    capabilities: u64,
+    registrar_acc_info: &AccountInfo,
+    registrar: &Registrar,
+    capability_registry_acc_info: Option<&AccountInfo>,
+) -> Result<(), RegistryError> {
+    if registrar.capability_registry == Pubkey::default() {
+        if capabilities >> serum_registry::accounts::registrar::MAX_CAPABILITIES != 0 {
+            return Err(RegistryErrorCode::InvalidCapabilityId)?;
+        }
+        return Ok(());
+    }
+
+    let capability_registry_acc_info =
+        capability_registry_acc_info.ok_or(RegistryErrorCode::InvalidCapabilityRegistry)?;
+    let capability_registry = capability_registry(
+        capability_registry_acc_info,
+        registrar_acc_info,
+        registrar,
+        program_id,
+    )?;
+    let max_capabilities = serum_registry::accounts::capability_registry::MAX_CAPABILITIES;
+    let is_registered = (0..max_capabilities as u8)
+        .filter(|id| capabilities & (1 << id) != 0)
+        .all(|id| capability_registry.get(id).is_some());
+    if !is_registered {
+        return Err(RegistryErrorCode::InvalidCapabilityId)?;
+    }
+
+    Ok(())
+}
+
+/// Access control on AddCapabilities and RemoveCapabilities, which differ
+/// only in how the bits are applied.
+pub fn update_capabilities(
+    program_id: &Pubkey,
+    capabilities: u64,
+    entity_acc_info: &AccountInfo,
+    entity_leader_acc_info: &AccountInfo,
+    registrar_acc_info: &AccountInfo,
+    capability_registry_acc_info: Option<&AccountInfo>,
+    multisig_signer_acc_infos: &[AccountInfo],
+) -> Result<Entity, RegistryError> {
+    let entity = entity_leader_multisig(
+        program_id,
+        entity_acc_info,
+        entity_leader_acc_info,
+        multisig_signer_acc_infos,
+    )?;
+    if entity.registrar != *registrar_acc_info.key {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+    let registrar = registrar(registrar_acc_info, program_id)?;
+    self::capabilities(
+        program_id,
+        capabilities,
+        registrar_acc_info,
+        &registrar,
+        capability_registry_acc_info,
+    )?;
+    Ok(entity)
+}
+
+pub fn multisig(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<Multisig, RegistryError> {
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    let multisig = Multisig::unpack(&acc_info.try_borrow_data()?)?;
+    if !multisig.initialized {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+
+    Ok(multisig)
+}
+
+/// Access control on any instruction invoking the SPL token program, so
+/// that transfers can't be routed through an impostor.
+pub fn token_program(acc_info: &AccountInfo) -> Result<(), RegistryError> {
+    if *acc_info.key != spl_token::ID {
+        return Err(RegistryErrorCode::InvalidTokenProgram)?;
+    }
+    Ok(())
+}
+
+pub fn clock(acc_info: &AccountInfo) -> Result<Clock, RegistryError> {
+    if *acc_info.key != solana_sdk::sysvar::clock::id() {
+        return Err(RegistryErrorCode::InvalidClockSysvar)?;
+    }
+    Clock::from_account_info(acc_info).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serum_registry::accounts::multisig;
+    use solana_sdk::clock::Epoch;
+    use solana_sdk::program_error::ProgramError;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+            Self {
+                key,
+                owner,
+                lamports: 0,
+                data,
+            }
+        }
+
+        fn info(&mut self, is_signer: bool) -> AccountInfo {
+            AccountInfo::new(
+                &self.key,
+                is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                Epoch::default(),
+            )
+        }
+    }
+
+    fn key(b: u8) -> Pubkey {
+        Pubkey::new_from_array([b; 32])
+    }
+
+    fn packed<T: Pack>(t: T) -> Vec<u8> {
+        let mut data = vec![0; t.size().unwrap() as usize];
+        T::pack(t, &mut data).unwrap();
+        data
+    }
+
+    fn assert_err<T>(result: Result<T, RegistryError>, code: RegistryErrorCode) {
+        match result {
+            Ok(_) => panic!("expected {:?}", code),
+            Err(e) => assert_eq!(ProgramError::from(e), ProgramError::Custom(code.into())),
+        }
+    }
+
+    fn registrar_account(program_id: Pubkey, initialized: bool) -> TestAccount {
+        let registrar = Registrar {
+            initialized,
+            authority: key(2),
+            ..Default::default()
+        };
+        TestAccount::new(key(1), program_id, packed(registrar))
+    }
+
+    fn entity_account(program_id: Pubkey, initialized: bool) -> TestAccount {
+        let entity = Entity {
+            initialized,
+            leader: key(4),
+            ..Default::default()
+        };
+        TestAccount::new(key(3), program_id, packed(entity))
+    }
+
+    #[test]
+    fn registrar_checks() {
+        let program_id = key(100);
+
+        let mut acc = registrar_account(key(101), true);
+        assert_err(
+            registrar(&acc.info(false), &program_id),
+            RegistryErrorCode::InvalidAccountOwner,
+        );
+
+        let mut acc = registrar_account(program_id, false);
+        assert_err(
+            registrar(&acc.info(false), &program_id),
+            RegistryErrorCode::NotInitialized,
+        );
+
+        let mut acc = registrar_account(program_id, true);
+        assert_eq!(
+            registrar(&acc.info(false), &program_id).unwrap().authority,
+            key(2)
+        );
+    }
+
+    #[test]
+    fn governance_checks() {
+        let program_id = key(100);
+        let mut registrar_acc = registrar_account(program_id, true);
+
+        let mut authority_acc = TestAccount::new(key(2), key(0), vec![]);
+        assert_err(
+            governance(
+                &program_id,
+                &registrar_acc.info(false),
+                &authority_acc.info(false),
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+
+        let mut imposter_acc = TestAccount::new(key(9), key(0), vec![]);
+        assert_err(
+            governance(
+                &program_id,
+                &registrar_acc.info(false),
+                &imposter_acc.info(true),
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+
+        let mut uninitialized_acc = registrar_account(program_id, false);
+        assert_err(
+            governance(
+                &program_id,
+                &uninitialized_acc.info(false),
+                &authority_acc.info(true),
+            ),
+            RegistryErrorCode::NotInitialized,
+        );
+
+        assert!(governance(
+            &program_id,
+            &registrar_acc.info(false),
+            &authority_acc.info(true)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn entity_checks() {
+        let program_id = key(100);
+
+        let mut acc = entity_account(key(101), true);
+        assert_err(
+            entity(&acc.info(false), &program_id),
+            RegistryErrorCode::InvalidAccountOwner,
+        );
+
+        let mut acc = entity_account(program_id, false);
+        assert_err(
+            entity(&acc.info(false), &program_id),
+            RegistryErrorCode::NotInitialized,
+        );
+
+        let mut acc = entity_account(program_id, true);
+        assert_eq!(
+            entity(&acc.info(false), &program_id).unwrap().leader,
+            key(4)
+        );
+    }
+
+    #[test]
+    fn entity_leader_checks() {
+        let program_id = key(100);
+        let mut entity_acc = entity_account(program_id, true);
+
+        let mut leader_acc = TestAccount::new(key(4), key(0), vec![]);
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &entity_acc.info(false),
+                &leader_acc.info(false),
+                &[],
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+
+        let mut imposter_acc = TestAccount::new(key(9), key(0), vec![]);
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &entity_acc.info(false),
+                &imposter_acc.info(true),
+                &[],
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+
+        let mut uninitialized_acc = entity_account(program_id, false);
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &uninitialized_acc.info(false),
+                &leader_acc.info(true),
+                &[],
+            ),
+            RegistryErrorCode::NotInitialized,
+        );
+
+        assert!(entity_leader_multisig(
+            &program_id,
+            &entity_acc.info(false),
+            &leader_acc.info(true),
+            &[],
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn entity_leader_multisig_checks() {
+        let program_id = key(100);
+        let mut entity_acc = entity_account(program_id, true);
+
+        // Single key leaders.
+        let mut leader_acc = TestAccount::new(key(4), key(0), vec![]);
+        assert!(entity_leader_multisig(
+            &program_id,
+            &entity_acc.info(false),
+            &leader_acc.info(true),
+            &[],
+        )
+        .is_ok());
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &entity_acc.info(false),
+                &leader_acc.info(false),
+                &[],
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+
+        // 2-of-3 Multisig leader.
+        let mut signers = [Pubkey::default(); multisig::MAX_SIGNERS];
+        signers[..3].copy_from_slice(&[key(10), key(11), key(12)]);
+        let mut multisig_acc = TestAccount::new(
+            key(4),
+            program_id,
+            packed(Multisig {
+                initialized: true,
+                m: 2,
+                n: 3,
+                signers,
+            }),
+        );
+        let mut signer_a = TestAccount::new(key(10), key(0), vec![]);
+        let mut signer_b = TestAccount::new(key(12), key(0), vec![]);
+        let mut outsider = TestAccount::new(key(13), key(0), vec![]);
+
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &entity_acc.info(false),
+                &multisig_acc.info(false),
+                &[signer_a.info(true), outsider.info(true)],
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &entity_acc.info(false),
+                &multisig_acc.info(false),
+                &[signer_a.info(true), signer_b.info(false)],
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+        assert!(entity_leader_multisig(
+            &program_id,
+            &entity_acc.info(false),
+            &multisig_acc.info(false),
+            &[signer_a.info(true), signer_b.info(true)],
+        )
+        .is_ok());
+
+        // The multisig must be the entity's leader.
+        let mut other_multisig_acc =
+            TestAccount::new(key(9), program_id, multisig_acc.data.clone());
+        assert_err(
+            entity_leader_multisig(
+                &program_id,
+                &entity_acc.info(false),
+                &other_multisig_acc.info(false),
+                &[signer_a.info(true), signer_b.info(true)],
+            ),
+            RegistryErrorCode::Unauthorized,
+        );
+    }
+
+    #[test]
+    fn member_checks() {
+        let program_id = key(100);
+        let member_account = |owner: Pubkey, initialized: bool| {
+            let member = Member {
+                initialized,
+                entity: key(3),
+                ..Default::default()
+            };
+            TestAccount::new(key(5), owner, packed(member))
+        };
+
+        let mut acc = member_account(key(101), true);
+        assert_err(
+            member(&acc.info(false), &program_id),
+            RegistryErrorCode::InvalidAccountOwner,
+        );
+
+        let mut acc = member_account(program_id, false);
+        assert_err(
+            member(&acc.info(false), &program_id),
+            RegistryErrorCode::NotInitialized,
+        );
+
+        let mut acc = member_account(program_id, true);
+        assert_eq!(
+            member(&acc.info(false), &program_id).unwrap().entity,
+            key(3)
+        );
+    }
+
+    #[test]
+    fn entity_metadata_checks() {
+        let program_id = key(100);
+        let metadata_account = |owner: Pubkey, initialized: bool| {
+            let metadata = EntityMetadata {
+                initialized,
+                entity: key(3),
+                name: "entity".to_string(),
+                ..Default::default()
+            };
+            TestAccount::new(key(6), owner, packed(metadata))
+        };
+        let mut entity_acc = TestAccount::new(key(3), program_id, vec![]);
+        let mut other_entity_acc = TestAccount::new(key(4), program_id, vec![]);
+
+        let mut acc = metadata_account(key(101), true);
+        assert_err(
+            entity_metadata(&acc.info(false), &entity_acc.info(false), &program_id),
+            RegistryErrorCode::InvalidAccountOwner,
+        );
+
+        let mut acc = metadata_account(program_id, false);
+        assert_err(
+            entity_metadata(&acc.info(false), &entity_acc.info(false), &program_id),
+            RegistryErrorCode::NotInitialized,
+        );
+
+        let mut acc = metadata_account(program_id, true);
+        assert_err(
+            entity_metadata(&acc.info(false), &other_entity_acc.info(false), &program_id),
+            RegistryErrorCode::Unauthorized,
+        );
+
+        let mut acc = metadata_account(program_id, true);
+        assert_eq!(
+            entity_metadata(&acc.info(false), &entity_acc.info(false), &program_id)
+                .unwrap()
+                .name,
+            "entity"
+        );
+    }
+
+    #[test]
+    fn capabilities_checks() {
+        let program_id = key(100);
+        let mut registrar_acc = registrar_account(program_id, true);
+
+        // Without a registry, any slot of the fee table.
+        let registrar = Registrar::default();
+        assert!(capabilities(
+            &program_id,
+            1 | 1 << 31,
+            &registrar_acc.info(false),
+            &registrar,
+            None
+        )
+        .is_ok());
+        assert_err(
+            capabilities(
+                &program_id,
+                1 << 32,
+                &registrar_acc.info(false),
+                &registrar,
+                None,
+            ),
+            RegistryErrorCode::InvalidCapabilityId,
+        );
+
+        // With one, only its entries.
+        let registrar = Registrar {
+            capability_registry: key(10),
+            ..Default::default()
+        };
+        let mut registry_acc = TestAccount::new(
+            key(10),
+            program_id,
+            packed(CapabilityRegistry {
+                initialized: true,
+                registrar: key(1),
+                capabilities: vec![serum_registry::accounts::CapabilityDescriptor {
+                    id: 40,
+                    fee_bps: 1,
+                    label_hash: [0; 32],
+                }],
+            }),
+        );
+        assert!(capabilities(
+            &program_id,
+            1 << 40,
+            &registrar_acc.info(false),
+            &registrar,
+            Some(&registry_acc.info(false)),
+        )
+        .is_ok());
+        assert_err(
+            capabilities(
+                &program_id,
+                1 << 40 | 1,
+                &registrar_acc.info(false),
+                &registrar,
+                Some(&registry_acc.info(false)),
+            ),
+            RegistryErrorCode::InvalidCapabilityId,
+        );
+        assert_err(
+            capabilities(
+                &program_id,
+                1 << 40,
+                &registrar_acc.info(false),
+                &registrar,
+                None,
+            ),
+            RegistryErrorCode::InvalidCapabilityRegistry,
+        );
+    }
+
+    #[test]
+    fn account_type_checks() {
+        let program_id = key(100);
+
+        // A Member with another account type's discriminator.
+        let mut data = packed(Member {
+            initialized: true,
+            ..Default::default()
+        });
+        data[..8].copy_from_slice(&serum_registry::accounts::entity::DISCRIMINATOR);
+        let mut acc = TestAccount::new(key(5), program_id, data);
+        match member(&acc.info(false), &program_id) {
+            Err(RegistryError::ProgramError(ProgramError::InvalidAccountData)) => {}
+            r => panic!("unexpected result: {:?}", r.map(|_| ())),
+        }
+
+        let mut data = packed(Entity {
+            initialized: true,
+            ..Default::default()
+        });
+        data[..8].copy_from_slice(&serum_registry::accounts::registrar::DISCRIMINATOR);
+        let mut acc = TestAccount::new(key(3), program_id, data);
+        match entity(&acc.info(false), &program_id) {
+            Err(RegistryError::ProgramError(ProgramError::InvalidAccountData)) => {}
+            r => panic!("unexpected result: {:?}", r.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn token_program_checks() {
+        let mut acc = TestAccount::new(key(8), key(9), vec![]);
+        assert_err(
+            token_program(&acc.info(false)),
+            RegistryErrorCode::InvalidTokenProgram,
+        );
+
+        let mut acc = TestAccount::new(spl_token::ID, key(9), vec![]);
+        assert!(token_program(&acc.info(false)).is_ok());
+    }
+
+    #[test]
+    fn clock_checks() {
+        let data = serum_common::pack::to_bytes(&Clock {
+            slot: 5,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut acc = TestAccount::new(key(7), solana_sdk::sysvar::id(), data.clone());
+        assert_err(
+            clock(&acc.info(false)),
+            RegistryErrorCode::InvalidClockSysvar,
+        );
+
+        let mut acc = TestAccount::new(
+            solana_sdk::sysvar::clock::id(),
+            solana_sdk::sysvar::id(),
+            data,
+        );
+        assert_eq!(clock(&acc.info(false)).unwrap().slot, 5);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Entity;
+use serum_registry::error::RegistryError;
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    capabilities: u64,
+) -> Result<(), RegistryError> {
+    trace!("handler: add_capabilities");
+
+    let acc_infos = &mut accounts.iter();
+
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let entity_leader_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+    let capability_registry_acc_info =
+        access_control::next_capability_registry(registrar_acc_info, acc_infos)?;
+    let multisig_signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        entity_acc_info,
+        entity_leader_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        capabilities,
+    })?;
+
+    Entity::unpack_mut(
+        &mut entity_acc_info.try_borrow_mut_data()?,
+        &mut |entity: &mut Entity| {
+            state_transition(StateTransitionRequest {
+                entity,
+                capabilities,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::CapabilitiesAdded {
+        entity: *entity_acc_info.key,
+        capabilities,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: add_capabilities");
+
+    let AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        entity_acc_info,
+        entity_leader_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        capabilities,
+    } = req;
+
+    let _ = access_control::update_capabilities(
+        program_id,
+        capabilities,
+        entity_acc_info,
+        entity_leader_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        multisig_signer_acc_infos,
+    )?;
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: add_capabilities");
+
+    let StateTransitionRequest {
+        entity,
+        capabilities,
+    } = req;
+
+    entity.capabilities |= capabilities;
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    multisig_signer_acc_infos: &'a [AccountInfo<'a>],
+    entity_acc_info: &'a AccountInfo<'a>,
+    entity_leader_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+    capability_registry_acc_info: Option<&'a AccountInfo<'a>>,
+    capabilities: u64,
+}
+
+struct StateTransitionRequest<'a> {
+    entity: &'a mut Entity,
+    capabilities: u64,
+}
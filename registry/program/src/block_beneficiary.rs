@@ -0,0 +1,97 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Registrar;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    beneficiary: Pubkey,
+) -> Result<(), RegistryError> {
+    trace!("handler: block_beneficiary");
+
+    let acc_infos = &mut accounts.iter();
+
+    let registrar_authority_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+        beneficiary,
+    })?;
+
+    Registrar::unpack_mut(
+        &mut registrar_acc_info.try_borrow_mut_data()?,
+        &mut |registrar: &mut Registrar| {
+            state_transition(StateTransitionRequest {
+                registrar,
+                beneficiary,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::BeneficiaryBlocked {
+        registrar: *registrar_acc_info.key,
+        beneficiary,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: block_beneficiary");
+
+    let AccessControlRequest {
+        program_id,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+        beneficiary,
+    } = req;
+
+    let _ =
+        access_control::governance(program_id, registrar_acc_info, registrar_authority_acc_info)?;
+    // The default key marks empty blocklist slots, so it can't be blocked.
+    if beneficiary == Pubkey::default() {
+        return Err(RegistryErrorCode::InvalidBeneficiary)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: block_beneficiary");
+
+    let StateTransitionRequest {
+        registrar,
+        beneficiary,
+    } = req;
+
+    if !registrar.block(beneficiary) {
+        return Err(RegistryErrorCode::BlocklistFull)?;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    registrar_authority_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+    beneficiary: Pubkey,
+}
+
+struct StateTransitionRequest<'a> {
+    registrar: &'a mut Registrar,
+    beneficiary: Pubkey,
+}
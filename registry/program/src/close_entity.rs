@@ -0,0 +1,143 @@
+use crate::access_control;
+use crate::emit;
+use serum_registry::addresses;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: close_entity");
+
+    let acc_infos = &mut accounts.iter();
+
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let authority_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+    let destination_acc_info = next_account_info(acc_infos)?;
+    let metadata_acc_info = next_account_info(acc_infos)?;
+    let multisig_signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        entity_acc_info,
+        authority_acc_info,
+        registrar_acc_info,
+        metadata_acc_info,
+    })?;
+
+    state_transition(StateTransitionRequest {
+        program_id,
+        entity_acc_info,
+        destination_acc_info,
+        metadata_acc_info,
+    })?;
+
+    emit(RegistryEvent::EntityClosed {
+        entity: *entity_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: close_entity");
+
+    let AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        entity_acc_info,
+        authority_acc_info,
+        registrar_acc_info,
+        metadata_acc_info,
+    } = req;
+
+    let entity = access_control::entity(entity_acc_info, program_id)?;
+    if entity.registrar != *registrar_acc_info.key {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    let is_authority =
+        authority_acc_info.is_signer && registrar.authority == *authority_acc_info.key;
+    if !is_authority {
+        let _ = access_control::entity_leader_multisig(
+            program_id,
+            entity_acc_info,
+            authority_acc_info,
+            multisig_signer_acc_infos,
+        )?;
+    }
+    if entity.amount != 0 || entity.mega_amount != 0 || entity.members != 0 {
+        return Err(RegistryErrorCode::EntityNotEmpty)?;
+    }
+
+    // Required even if the metadata was never created, so that it can't be
+    // left behind describing a closed entity.
+    let (address, _nonce) = addresses::metadata(entity_acc_info.key, program_id)
+        .map_err(|_| RegistryErrorCode::InvalidMetadata)?;
+    if *metadata_acc_info.key != address {
+        return Err(RegistryErrorCode::InvalidMetadata)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: close_entity");
+
+    let StateTransitionRequest {
+        program_id,
+        entity_acc_info,
+        destination_acc_info,
+        metadata_acc_info,
+    } = req;
+
+    close(entity_acc_info, destination_acc_info)?;
+    if metadata_acc_info.owner == program_id {
+        close(metadata_acc_info, destination_acc_info)?;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+// Clears the data, marking the account uninitialized, before moving the
+// lamports out so the account is never left funded with stale state.
+fn close(acc_info: &AccountInfo, destination_acc_info: &AccountInfo) -> Result<(), RegistryError> {
+    for b in acc_info.try_borrow_mut_data()?.iter_mut() {
+        *b = 0;
+    }
+
+    let lamports = acc_info.lamports();
+    let destination_lamports = destination_acc_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(RegistryErrorCode::Overflow)?;
+    **acc_info.try_borrow_mut_lamports()? = 0;
+    **destination_acc_info.try_borrow_mut_lamports()? = destination_lamports;
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    multisig_signer_acc_infos: &'a [AccountInfo<'a>],
+    entity_acc_info: &'a AccountInfo<'a>,
+    authority_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+    metadata_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    program_id: &'a Pubkey,
+    entity_acc_info: &'a AccountInfo<'a>,
+    destination_acc_info: &'a AccountInfo<'a>,
+    metadata_acc_info: &'a AccountInfo<'a>,
+}
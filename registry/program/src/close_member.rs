@@ -0,0 +1,120 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Entity;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: close_member");
+
+    let acc_infos = &mut accounts.iter();
+
+    let member_acc_info = next_account_info(acc_infos)?;
+    let beneficiary_acc_info = next_account_info(acc_infos)?;
+    let destination_acc_info = next_account_info(acc_infos)?;
+    let entity_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        entity_acc_info,
+    })?;
+
+    Entity::unpack_mut(
+        &mut entity_acc_info.try_borrow_mut_data()?,
+        &mut |entity: &mut Entity| {
+            state_transition(StateTransitionRequest {
+                entity,
+                member_acc_info,
+                destination_acc_info,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::MemberClosed {
+        member: *member_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: close_member");
+
+    let AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        entity_acc_info,
+    } = req;
+
+    if !beneficiary_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    let member = access_control::member(member_acc_info, program_id)?;
+    if !member.authorities.is_beneficiary(beneficiary_acc_info.key) {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    if member.amount != 0 || member.mega_amount != 0 {
+        return Err(RegistryErrorCode::MemberNotEmpty)?;
+    }
+    if member.entity != *entity_acc_info.key {
+        return Err(RegistryErrorCode::MemberEntityMismatch)?;
+    }
+    let _ = access_control::entity(entity_acc_info, program_id)?;
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: close_member");
+
+    let StateTransitionRequest {
+        entity,
+        member_acc_info,
+        destination_acc_info,
+    } = req;
+
+    entity.remove_member()?;
+
+    // Clear the data, marking the account uninitialized, before moving the
+    // lamports out so the account is never left funded with stale state.
+    for b in member_acc_info.try_borrow_mut_data()?.iter_mut() {
+        *b = 0;
+    }
+
+    let lamports = member_acc_info.lamports();
+    let destination_lamports = destination_acc_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(RegistryErrorCode::Overflow)?;
+    **member_acc_info.try_borrow_mut_lamports()? = 0;
+    **destination_acc_info.try_borrow_mut_lamports()? = destination_lamports;
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    member_acc_info: &'a AccountInfo<'a>,
+    beneficiary_acc_info: &'a AccountInfo<'a>,
+    entity_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a, 'b> {
+    entity: &'b mut Entity,
+    member_acc_info: &'a AccountInfo<'a>,
+    destination_acc_info: &'a AccountInfo<'a>,
+}
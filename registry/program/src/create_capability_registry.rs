@@ -0,0 +1,130 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::{CapabilityDescriptor, CapabilityRegistry, Registrar};
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: create_capability_registry");
+
+    let acc_infos = &mut accounts.iter();
+
+    let capability_registry_acc_info = next_account_info(acc_infos)?;
+    let registrar_authority_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        capability_registry_acc_info,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+    })?;
+
+    CapabilityRegistry::unpack_mut(
+        &mut capability_registry_acc_info.try_borrow_mut_data()?,
+        &mut |capability_registry: &mut CapabilityRegistry| {
+            Registrar::unpack_mut(
+                &mut registrar_acc_info.try_borrow_mut_data()?,
+                &mut |registrar: &mut Registrar| {
+                    state_transition(StateTransitionRequest {
+                        capability_registry,
+                        capability_registry_acc_info,
+                        registrar,
+                        registrar_acc_info,
+                    })
+                    .map_err(Into::into)
+                },
+            )
+        },
+    )?;
+
+    emit(RegistryEvent::CapabilityRegistryCreated {
+        registrar: *registrar_acc_info.key,
+        capability_registry: *capability_registry_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: create_capability_registry");
+
+    let AccessControlRequest {
+        program_id,
+        capability_registry_acc_info,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+    } = req;
+
+    let registrar =
+        access_control::governance(program_id, registrar_acc_info, registrar_authority_acc_info)?;
+    if registrar.capability_registry != Pubkey::default() {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+
+    if capability_registry_acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+    let capability_registry =
+        CapabilityRegistry::unpack(&capability_registry_acc_info.try_borrow_data()?)?;
+    if capability_registry.initialized {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: create_capability_registry");
+
+    let StateTransitionRequest {
+        capability_registry,
+        capability_registry_acc_info,
+        registrar,
+        registrar_acc_info,
+    } = req;
+
+    capability_registry.initialized = true;
+    capability_registry.registrar = *registrar_acc_info.key;
+    capability_registry.capabilities = vec![];
+
+    // Migrate the fee table. Unset slots are indistinguishable from zero fee
+    // capabilities, so only nonzero fees carry over.
+    for (id, fee_bps) in registrar.capabilities_fees_bps.iter().enumerate() {
+        if *fee_bps != 0 {
+            capability_registry.set(CapabilityDescriptor {
+                id: id as u8,
+                fee_bps: *fee_bps,
+                label_hash: [0; 32],
+            });
+        }
+    }
+
+    registrar.capability_registry = *capability_registry_acc_info.key;
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    capability_registry_acc_info: &'a AccountInfo<'a>,
+    registrar_authority_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    capability_registry: &'a mut CapabilityRegistry,
+    capability_registry_acc_info: &'a AccountInfo<'a>,
+    registrar: &'a mut Registrar,
+    registrar_acc_info: &'a AccountInfo<'a>,
+}
@@ -1,28 +1,37 @@
+use crate::access_control;
+use crate::emit;
 use serum_common::pack::Pack;
 use serum_registry::accounts::{Entity, StakeKind};
 use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
-    capabilities: u32,
+    capabilities: u64,
     stake_kind: StakeKind,
 ) -> Result<(), RegistryError> {
-    info!("handler: create_entity");
+    trace!("handler: create_entity");
 
     let acc_infos = &mut accounts.iter();
 
     let entity_acc_info = next_account_info(acc_infos)?;
     let entity_leader_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
     let rent_acc_info = next_account_info(acc_infos)?;
+    let capability_registry_acc_info =
+        access_control::next_capability_registry(registrar_acc_info, acc_infos)?;
 
     access_control(AccessControlRequest {
+        program_id,
         entity_acc_info,
         entity_leader_acc_info,
+        registrar_acc_info,
         rent_acc_info,
+        capability_registry_acc_info,
+        capabilities,
         stake_kind,
     })?;
 
@@ -31,6 +40,7 @@ pub fn handler<'a>(
         &mut |entity: &mut Entity| {
             state_transition(StateTransitionRequest {
                 leader: entity_leader_acc_info.key,
+                registrar: registrar_acc_info.key,
                 entity,
                 capabilities,
                 stake_kind,
@@ -39,16 +49,26 @@ pub fn handler<'a>(
         },
     )?;
 
+    emit(RegistryEvent::EntityCreated {
+        entity: *entity_acc_info.key,
+        registrar: *registrar_acc_info.key,
+        leader: *entity_leader_acc_info.key,
+    })?;
+
     Ok(())
 }
 
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
-    info!("access-control: create_entity");
+    trace!("access-control: create_entity");
 
     let AccessControlRequest {
+        program_id,
         entity_acc_info,
         entity_leader_acc_info,
+        registrar_acc_info,
         rent_acc_info,
+        capability_registry_acc_info,
+        capabilities,
         stake_kind,
     } = req;
 
@@ -57,45 +77,84 @@ fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
         return Err(RegistryErrorCode::MustBeDelegated)?;
     }
 
+    // Leader authorization.
+    if !entity_leader_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+
+    // Entity checks. Creation would otherwise overwrite a live entity's
+    // leader and stake.
+    if entity_acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+    let entity = Entity::unpack(&entity_acc_info.try_borrow_data()?)?;
+    if entity.initialized {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.paused {
+        return Err(RegistryErrorCode::Paused)?;
+    }
+    let is_authority = registrar.authority == *entity_leader_acc_info.key;
+    if registrar.separate_leader_and_authority && is_authority {
+        return Err(RegistryErrorCode::LeaderIsRegistrarAuthority)?;
+    }
+    access_control::capabilities(
+        program_id,
+        capabilities,
+        registrar_acc_info,
+        &registrar,
+        capability_registry_acc_info,
+    )?;
+
     // todo
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
-    info!("state-transition: create_entity");
+    trace!("state-transition: create_entity");
 
     let StateTransitionRequest {
         entity,
         leader,
+        registrar,
         capabilities,
         stake_kind,
     } = req;
 
     entity.initialized = true;
+    entity.registrar = *registrar;
     entity.leader = *leader;
     entity.amount = 0;
     entity.mega_amount = 0;
     entity.capabilities = capabilities;
     entity.stake_kind = stake_kind;
+    entity.last_heartbeat_slot = 0;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
 
 struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
     entity_acc_info: &'a AccountInfo<'a>,
     entity_leader_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
     rent_acc_info: &'a AccountInfo<'a>,
+    capability_registry_acc_info: Option<&'a AccountInfo<'a>>,
+    capabilities: u64,
     stake_kind: StakeKind,
 }
 
 struct StateTransitionRequest<'a, 'b> {
     entity: &'b mut Entity,
     leader: &'a Pubkey,
-    capabilities: u32,
+    registrar: &'a Pubkey,
+    capabilities: u64,
     stake_kind: StakeKind,
 }
@@ -0,0 +1,215 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::{metadata, EntityMetadata};
+use serum_registry::addresses;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction;
+use solana_sdk::sysvar::rent::Rent;
+use solana_sdk::sysvar::Sysvar;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    name: String,
+    url: String,
+    description: String,
+) -> Result<(), RegistryError> {
+    trace!("handler: create_metadata");
+
+    let acc_infos = &mut accounts.iter();
+
+    let metadata_acc_info = next_account_info(acc_infos)?;
+    let entity_leader_acc_info = next_account_info(acc_infos)?;
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let payer_acc_info = next_account_info(acc_infos)?;
+    let rent_acc_info = next_account_info(acc_infos)?;
+    let system_program_acc_info = next_account_info(acc_infos)?;
+    let multisig_signer_acc_infos = acc_infos.as_slice();
+
+    let nonce = access_control(AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        metadata_acc_info,
+        entity_leader_acc_info,
+        entity_acc_info,
+    })?;
+
+    create_account(CreateAccountRequest {
+        program_id,
+        metadata_acc_info,
+        entity_acc_info,
+        payer_acc_info,
+        rent_acc_info,
+        system_program_acc_info,
+        nonce,
+    })?;
+
+    EntityMetadata::unpack_mut(
+        &mut metadata_acc_info.try_borrow_mut_data()?,
+        &mut |metadata: &mut EntityMetadata| {
+            state_transition(StateTransitionRequest {
+                metadata,
+                entity: entity_acc_info.key,
+                name: name.clone(),
+                url: url.clone(),
+                description: description.clone(),
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::MetadataUpdated {
+        entity: *entity_acc_info.key,
+        metadata: *metadata_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<u8, RegistryError> {
+    trace!("access-control: create_metadata");
+
+    let AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        metadata_acc_info,
+        entity_leader_acc_info,
+        entity_acc_info,
+    } = req;
+
+    let _ = access_control::entity_leader_multisig(
+        program_id,
+        entity_acc_info,
+        entity_leader_acc_info,
+        multisig_signer_acc_infos,
+    )?;
+
+    // The metadata lives at the address derived from the entity, so there's
+    // at most one and clients can find it without a scan.
+    let (address, nonce) = addresses::metadata(entity_acc_info.key, program_id)
+        .map_err(|_| RegistryErrorCode::InvalidMetadata)?;
+    if *metadata_acc_info.key != address {
+        return Err(RegistryErrorCode::InvalidMetadata)?;
+    }
+    if metadata_acc_info.try_data_len()? != 0 {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(nonce)
+}
+
+// Allocates the rent exempt metadata account at its derived address, signing
+// for it with the entity's seeds.
+//
+// Anyone can send lamports to the derived address ahead of time, which would
+// make `create_account` fail, so a funded address is topped up, allocated,
+// and assigned in separate steps instead.
+fn create_account(req: CreateAccountRequest) -> Result<(), RegistryError> {
+    trace!("create-account: create_metadata");
+
+    let CreateAccountRequest {
+        program_id,
+        metadata_acc_info,
+        entity_acc_info,
+        payer_acc_info,
+        rent_acc_info,
+        system_program_acc_info,
+        nonce,
+    } = req;
+
+    let rent = Rent::from_account_info(rent_acc_info)?;
+    let required_lamports = rent.minimum_balance(metadata::SIZE as usize);
+    let acc_infos = &[
+        payer_acc_info.clone(),
+        metadata_acc_info.clone(),
+        system_program_acc_info.clone(),
+    ];
+    let signer_seeds = addresses::metadata_signer_seeds(entity_acc_info.key, &nonce);
+
+    let lamports = metadata_acc_info.lamports();
+    if lamports == 0 {
+        let create_acc_instr = system_instruction::create_account(
+            payer_acc_info.key,
+            metadata_acc_info.key,
+            required_lamports,
+            metadata::SIZE,
+            program_id,
+        );
+        solana_sdk::program::invoke_signed(&create_acc_instr, acc_infos, &[&signer_seeds])?;
+    } else {
+        if lamports < required_lamports {
+            let transfer_instr = system_instruction::transfer(
+                payer_acc_info.key,
+                metadata_acc_info.key,
+                required_lamports - lamports,
+            );
+            solana_sdk::program::invoke(&transfer_instr, acc_infos)?;
+        }
+        let allocate_instr = system_instruction::allocate(metadata_acc_info.key, metadata::SIZE);
+        solana_sdk::program::invoke_signed(&allocate_instr, acc_infos, &[&signer_seeds])?;
+        let assign_instr = system_instruction::assign(metadata_acc_info.key, program_id);
+        solana_sdk::program::invoke_signed(&assign_instr, acc_infos, &[&signer_seeds])?;
+    }
+
+    trace!("create-account: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: create_metadata");
+
+    let StateTransitionRequest {
+        metadata,
+        entity,
+        name,
+        url,
+        description,
+    } = req;
+
+    metadata.initialized = true;
+    metadata.entity = *entity;
+    metadata.name = name;
+    metadata.url = url;
+    metadata.description = description;
+
+    if !metadata.is_valid() {
+        return Err(RegistryErrorCode::InvalidMetadata)?;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    multisig_signer_acc_infos: &'a [AccountInfo<'a>],
+    metadata_acc_info: &'a AccountInfo<'a>,
+    entity_leader_acc_info: &'a AccountInfo<'a>,
+    entity_acc_info: &'a AccountInfo<'a>,
+}
+
+struct CreateAccountRequest<'a> {
+    program_id: &'a Pubkey,
+    metadata_acc_info: &'a AccountInfo<'a>,
+    entity_acc_info: &'a AccountInfo<'a>,
+    payer_acc_info: &'a AccountInfo<'a>,
+    rent_acc_info: &'a AccountInfo<'a>,
+    system_program_acc_info: &'a AccountInfo<'a>,
+    nonce: u8,
+}
+
+struct StateTransitionRequest<'a, 'b> {
+    metadata: &'b mut EntityMetadata,
+    entity: &'a Pubkey,
+    name: String,
+    url: String,
+    description: String,
+}
@@ -0,0 +1,104 @@
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::{multisig, Multisig};
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    m: u8,
+    signers: Vec<Pubkey>,
+) -> Result<(), RegistryError> {
+    trace!("handler: create_multisig");
+
+    let acc_infos = &mut accounts.iter();
+
+    let multisig_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        multisig_acc_info,
+        m,
+        signers: &signers,
+    })?;
+
+    Multisig::unpack_mut(
+        &mut multisig_acc_info.try_borrow_mut_data()?,
+        &mut |multisig: &mut Multisig| {
+            state_transition(StateTransitionRequest {
+                multisig,
+                m,
+                signers: &signers,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::MultisigCreated {
+        multisig: *multisig_acc_info.key,
+        m,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: create_multisig");
+
+    let AccessControlRequest {
+        program_id,
+        multisig_acc_info,
+        m,
+        signers,
+    } = req;
+
+    if multisig_acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+    let multisig = Multisig::unpack(&multisig_acc_info.try_borrow_data()?)?;
+    if multisig.initialized {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+    if !multisig::is_valid(m, signers) {
+        return Err(RegistryErrorCode::InvalidMultisig)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: create_multisig");
+
+    let StateTransitionRequest {
+        multisig,
+        m,
+        signers,
+    } = req;
+
+    multisig.initialized = true;
+    multisig.m = m;
+    multisig.n = signers.len() as u8;
+    multisig.signers[..signers.len()].copy_from_slice(signers);
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    multisig_acc_info: &'a AccountInfo<'a>,
+    m: u8,
+    signers: &'a [Pubkey],
+}
+
+struct StateTransitionRequest<'a, 'b> {
+    multisig: &'b mut Multisig,
+    m: u8,
+    signers: &'a [Pubkey],
+}
@@ -1,6 +1,6 @@
+use crate::access_control;
 use serum_registry::error::{RegistryError, RegistryErrorCode};
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 // TODO: update to transfer funds to the pool.
@@ -9,7 +9,7 @@ pub fn handler<'a>(
     accounts: &'a [AccountInfo<'a>],
     donate_amount: u64,
 ) -> Result<(), RegistryError> {
-    info!("handler: donate");
+    trace!("handler: donate");
 
     let acc_infos = &mut accounts.iter();
 
@@ -39,7 +39,7 @@ pub fn handler<'a>(
 }
 
 fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), RegistryError> {
-    info!("access-control: donate");
+    trace!("access-control: donate");
 
     let AccessControlRequest {
         donator_authority_acc_info,
@@ -49,15 +49,17 @@ fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), RegistryError
         token_program_acc_info,
     } = req;
 
+    access_control::token_program(token_program_acc_info)?;
+
     // todo
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition<'a>(req: StateTransitionRequest<'a>) -> Result<(), RegistryError> {
-    info!("state-transition: donate");
+    trace!("state-transition: donate");
 
     let StateTransitionRequest {
         donator_authority_acc_info,
@@ -68,7 +70,7 @@ fn state_transition<'a>(req: StateTransitionRequest<'a>) -> Result<(), RegistryE
         donate_amount,
     } = req;
 
-    info!("invoke SPL token transfer");
+    trace!("invoke SPL token transfer");
 
     let donate_instruction = spl_token::instruction::transfer(
         &spl_token::ID,
@@ -89,7 +91,7 @@ fn state_transition<'a>(req: StateTransitionRequest<'a>) -> Result<(), RegistryE
         &[],
     )?;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
@@ -1,6 +1,5 @@
 use serum_registry::error::{RegistryError, RegistryErrorCode};
 use solana_sdk::account_info::AccountInfo;
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
@@ -8,7 +7,7 @@ pub fn handler<'a>(
     accounts: &'a [AccountInfo<'a>],
 ) -> Result<(), RegistryError> {
     // todo
-    info!("handler: complete_stake_withdrawl");
+    trace!("handler: complete_stake_withdrawl");
 
     access_control(AccessControlRequest {})?;
 
@@ -18,22 +17,22 @@ pub fn handler<'a>(
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
     // todo
 
-    info!("access-control: complete_stake_withdrawal");
+    trace!("access-control: complete_stake_withdrawal");
 
     let AccessControlRequest {} = req;
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
     // todo
-    info!("state-transition: complete_stake_withdrawal");
+    trace!("state-transition: complete_stake_withdrawal");
 
     let StateTransitionRequest {} = req;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
@@ -0,0 +1,90 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Entity;
+use serum_registry::error::RegistryError;
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: heartbeat");
+
+    let acc_infos = &mut accounts.iter();
+
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let entity_leader_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let multisig_signer_acc_infos = acc_infos.as_slice();
+    let clock_slot = access_control::clock(clock_acc_info)?.slot;
+
+    access_control(AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        entity_acc_info,
+        entity_leader_acc_info,
+    })?;
+
+    Entity::unpack_mut(
+        &mut entity_acc_info.try_borrow_mut_data()?,
+        &mut |entity: &mut Entity| {
+            state_transition(StateTransitionRequest { entity, clock_slot }).map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::Heartbeat {
+        entity: *entity_acc_info.key,
+        slot: clock_slot,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: heartbeat");
+
+    let AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        entity_acc_info,
+        entity_leader_acc_info,
+    } = req;
+
+    let _ = access_control::entity_leader_multisig(
+        program_id,
+        entity_acc_info,
+        entity_leader_acc_info,
+        multisig_signer_acc_infos,
+    )?;
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: heartbeat");
+
+    let StateTransitionRequest { entity, clock_slot } = req;
+
+    entity.last_heartbeat_slot = clock_slot;
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    multisig_signer_acc_infos: &'a [AccountInfo<'a>],
+    entity_acc_info: &'a AccountInfo<'a>,
+    entity_leader_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    entity: &'a mut Entity,
+    clock_slot: u64,
+}
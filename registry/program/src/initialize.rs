@@ -1,8 +1,9 @@
+use crate::emit;
 use serum_common::pack::Pack;
 use serum_registry::accounts::{registrar, Registrar};
 use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
@@ -10,8 +11,9 @@ pub fn handler<'a>(
     accounts: &'a [AccountInfo<'a>],
     authority: Pubkey,
     withdrawal_timelock: u64,
+    separate_leader_and_authority: bool,
 ) -> Result<(), RegistryError> {
-    info!("handler: initialize");
+    trace!("handler: initialize");
 
     let acc_infos = &mut accounts.iter();
 
@@ -19,6 +21,7 @@ pub fn handler<'a>(
     let rent_acc_info = next_account_info(acc_infos)?;
 
     access_control(AccessControlRequest {
+        program_id,
         registrar_acc_info,
         rent_acc_info,
     })?;
@@ -30,49 +33,70 @@ pub fn handler<'a>(
                 registrar,
                 authority,
                 withdrawal_timelock,
+                separate_leader_and_authority,
             })
             .map_err(Into::into)
         },
     )?;
 
+    emit(RegistryEvent::RegistrarInitialized {
+        registrar: *registrar_acc_info.key,
+        authority,
+    })?;
+
     Ok(())
 }
 
 fn access_control<'a>(req: AccessControlRequest<'a>) -> Result<(), RegistryError> {
-    info!("access-control: initialize");
+    trace!("access-control: initialize");
 
     let AccessControlRequest {
+        program_id,
         registrar_acc_info,
         rent_acc_info,
     } = req;
 
+    if registrar_acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+    let registrar = Registrar::unpack(&registrar_acc_info.try_borrow_data()?)?;
+    if registrar.initialized {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+
     // todo
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition<'a>(req: StateTransitionRequest<'a>) -> Result<(), RegistryError> {
-    info!("state-transition: initialize");
+    trace!("state-transition: initialize");
 
     let StateTransitionRequest {
         registrar,
         authority,
         withdrawal_timelock,
+        separate_leader_and_authority,
     } = req;
 
     registrar.initialized = true;
     registrar.capabilities_fees_bps = [0; 32];
     registrar.authority = authority;
+    registrar.pending_authority = Pubkey::default();
     registrar.withdrawal_timelock = withdrawal_timelock;
+    registrar.separate_leader_and_authority = separate_leader_and_authority;
+    registrar.paused = false;
+    registrar.capability_registry = Pubkey::default();
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
 
 struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
     registrar_acc_info: &'a AccountInfo<'a>,
     rent_acc_info: &'a AccountInfo<'a>,
 }
@@ -81,4 +105,5 @@ struct StateTransitionRequest<'a> {
     registrar: &'a mut Registrar,
     authority: Pubkey,
     withdrawal_timelock: u64,
+    separate_leader_and_authority: bool,
 }
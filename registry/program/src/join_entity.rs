@@ -1,8 +1,10 @@
+use crate::access_control;
+use crate::emit;
 use serum_common::pack::Pack;
-use serum_registry::accounts::Member;
+use serum_registry::accounts::{Entity, Member, MemberAuthorities};
 use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
@@ -11,82 +13,142 @@ pub fn handler<'a>(
     beneficiary: Pubkey,
     delegate: Pubkey,
 ) -> Result<(), RegistryError> {
-    info!("handler: join_entity");
+    trace!("handler: join_entity");
 
     let acc_infos = &mut accounts.iter();
 
     let member_acc_info = next_account_info(acc_infos)?;
     let entity_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
     let rent_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let clock_slot = access_control::clock(clock_acc_info)?.slot;
 
     access_control(AccessControlRequest {
+        program_id,
         member_acc_info,
         entity_acc_info,
+        registrar_acc_info,
         rent_acc_info,
+        beneficiary,
+        clock_slot,
     })?;
 
-    Member::unpack_mut(
-        &mut member_acc_info.try_borrow_mut_data()?,
-        &mut |member: &mut Member| {
-            state_transition(StateTransitionRequest {
-                member,
-                beneficiary,
-                delegate,
-                entity_acc_info,
-            })
-            .map_err(Into::into)
+    Entity::unpack_mut(
+        &mut entity_acc_info.try_borrow_mut_data()?,
+        &mut |entity: &mut Entity| {
+            Member::unpack_mut(
+                &mut member_acc_info.try_borrow_mut_data()?,
+                &mut |member: &mut Member| {
+                    state_transition(StateTransitionRequest {
+                        member,
+                        entity,
+                        beneficiary,
+                        delegate,
+                        entity_acc_info,
+                    })
+                    .map_err(Into::into)
+                },
+            )
         },
     )?;
 
+    emit(RegistryEvent::MemberJoined {
+        member: *member_acc_info.key,
+        entity: *entity_acc_info.key,
+        beneficiary,
+    })?;
+
     Ok(())
 }
 
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
-    info!("access-control: join_entity");
+    trace!("access-control: join_entity");
 
     let AccessControlRequest {
+        program_id,
         member_acc_info,
         entity_acc_info,
+        registrar_acc_info,
         rent_acc_info,
+        beneficiary,
+        clock_slot,
     } = req;
 
+    let entity = access_control::entity(entity_acc_info, program_id)?;
+    if entity.registrar != *registrar_acc_info.key {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.paused {
+        return Err(RegistryErrorCode::Paused)?;
+    }
+    if registrar.is_blocked(&beneficiary) {
+        return Err(RegistryErrorCode::BeneficiaryBlocked)?;
+    }
+    // Members shouldn't delegate to a leader that stopped attesting it's
+    // running a node.
+    if entity.is_stale(clock_slot, registrar.heartbeat_timeout) {
+        return Err(RegistryErrorCode::StaleEntity)?;
+    }
+
+    // Member checks. Joining would otherwise reset a live member's balances
+    // and move it to this entity without leaving its current one.
+    if member_acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+    let member = Member::unpack(&member_acc_info.try_borrow_data()?)?;
+    if member.initialized {
+        return Err(RegistryErrorCode::AlreadyInitialized)?;
+    }
+
     // todo
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
-    info!("state-transition: join_entity");
+    trace!("state-transition: join_entity");
 
     let StateTransitionRequest {
         member,
+        entity,
         beneficiary,
         delegate,
         entity_acc_info,
     } = req;
 
+    entity.add_member()?;
+
     member.initialized = true;
     member.entity = *entity_acc_info.key;
-    member.beneficiary = beneficiary;
-    member.delegate = delegate;
+    member.authorities = MemberAuthorities {
+        beneficiary,
+        delegate,
+    };
     member.amount = 0;
     member.mega_amount = 0;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
 
 struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
     member_acc_info: &'a AccountInfo<'a>,
     entity_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
     rent_acc_info: &'a AccountInfo<'a>,
+    beneficiary: Pubkey,
+    clock_slot: u64,
 }
 
 struct StateTransitionRequest<'a, 'b> {
     member: &'b mut Member,
+    entity: &'b mut Entity,
     beneficiary: Pubkey,
     delegate: Pubkey,
     entity_acc_info: &'a AccountInfo<'a>,
@@ -4,21 +4,64 @@
 
 use serum_common::pack::Pack;
 use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::{self, RegistryEvent};
 use serum_registry::instruction::RegistryInstruction;
 use solana_sdk::account_info::AccountInfo;
 use solana_sdk::entrypoint::ProgramResult;
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
+// Logs are charged against the instruction's compute budget, so the
+// per-handler tracing is only emitted when built with `verbose-logs`. With
+// it, every instruction emits seven of these: entry and exit from the
+// dispatcher, the handler, and a start/success pair for both access control
+// and the state transition.
+macro_rules! trace {
+    ($msg:expr) => {
+        if cfg!(feature = "verbose-logs") {
+            solana_sdk::info!($msg);
+        }
+    };
+}
+
+// Logs the given event for clients to decode with
+// `serum_registry::events::decode_logs`. Unlike `trace!`, events are always
+// emitted since clients depend on them.
+fn emit(event: RegistryEvent) -> Result<(), RegistryError> {
+    let log = events::encode(&event)?;
+    solana_sdk::info!(&log);
+    Ok(())
+}
+
+mod accept_authority;
+pub(crate) mod access_control;
+mod add_capabilities;
+mod block_beneficiary;
+mod close_entity;
+mod close_member;
+mod create_capability_registry;
 mod create_entity;
+mod create_metadata;
+mod create_multisig;
 mod donate;
 mod end_stake_withdrawal;
+mod heartbeat;
 mod initialize;
 mod join_entity;
+mod migrate_account;
+mod pause;
 mod register_capability;
+mod remove_capabilities;
+mod set_beneficiary;
+mod set_capability;
+mod set_delegate;
+mod set_pending_authority;
 mod stake;
 mod start_stake_withdrawal;
+mod switch_entity;
+mod unblock_beneficiary;
 mod update_entity;
+mod update_metadata;
+mod update_registrar;
 
 solana_sdk::entrypoint!(process_instruction);
 fn process_instruction<'a>(
@@ -26,7 +69,7 @@ fn process_instruction<'a>(
     accounts: &'a [AccountInfo<'a>],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    info!("process-instruction");
+    trace!("process-instruction");
 
     let instruction: RegistryInstruction = RegistryInstruction::unpack(instruction_data)
         .map_err(|_| RegistryError::ErrorCode(RegistryErrorCode::WrongSerialization))?;
@@ -35,7 +78,14 @@ fn process_instruction<'a>(
         RegistryInstruction::Initialize {
             authority,
             withdrawal_timelock,
-        } => initialize::handler(program_id, accounts, authority, withdrawal_timelock),
+            separate_leader_and_authority,
+        } => initialize::handler(
+            program_id,
+            accounts,
+            authority,
+            withdrawal_timelock,
+            separate_leader_and_authority,
+        ),
         RegistryInstruction::RegisterCapability {
             capability_id,
             capability_fee_bps,
@@ -67,11 +117,81 @@ fn process_instruction<'a>(
         RegistryInstruction::Donate { amount } => Err(RegistryError::ErrorCode(
             RegistryErrorCode::NotReadySeeNextMajorVersion,
         )),
+        RegistryInstruction::Heartbeat => heartbeat::handler(program_id, accounts),
+        RegistryInstruction::AddCapabilities { capabilities } => {
+            add_capabilities::handler(program_id, accounts, capabilities)
+        }
+        RegistryInstruction::RemoveCapabilities { capabilities } => {
+            remove_capabilities::handler(program_id, accounts, capabilities)
+        }
+        RegistryInstruction::BlockBeneficiary { beneficiary } => {
+            block_beneficiary::handler(program_id, accounts, beneficiary)
+        }
+        RegistryInstruction::UnblockBeneficiary { beneficiary } => {
+            unblock_beneficiary::handler(program_id, accounts, beneficiary)
+        }
+        RegistryInstruction::SetPendingAuthority { pending_authority } => {
+            set_pending_authority::handler(program_id, accounts, pending_authority)
+        }
+        RegistryInstruction::AcceptAuthority => accept_authority::handler(program_id, accounts),
+        RegistryInstruction::Pause => pause::handler(program_id, accounts, true),
+        RegistryInstruction::Unpause => pause::handler(program_id, accounts, false),
+        RegistryInstruction::UpdateRegistrar {
+            withdrawal_timelock,
+            separate_leader_and_authority,
+            heartbeat_timeout,
+        } => update_registrar::handler(
+            program_id,
+            accounts,
+            withdrawal_timelock,
+            separate_leader_and_authority,
+            heartbeat_timeout,
+        ),
+        RegistryInstruction::CloseMember => close_member::handler(program_id, accounts),
+        RegistryInstruction::CloseEntity => close_entity::handler(program_id, accounts),
+        RegistryInstruction::CreateMetadata {
+            name,
+            url,
+            description,
+        } => create_metadata::handler(program_id, accounts, name, url, description),
+        RegistryInstruction::UpdateMetadata {
+            name,
+            url,
+            description,
+        } => update_metadata::handler(program_id, accounts, name, url, description),
+        RegistryInstruction::SetDelegate { delegate } => {
+            set_delegate::handler(program_id, accounts, delegate)
+        }
+        RegistryInstruction::RevokeDelegate => {
+            set_delegate::handler(program_id, accounts, Pubkey::default())
+        }
+        RegistryInstruction::SwitchEntity => switch_entity::handler(program_id, accounts),
+        RegistryInstruction::CreateMultisig { m, signers } => {
+            create_multisig::handler(program_id, accounts, m, signers)
+        }
+        RegistryInstruction::SetBeneficiary { beneficiary } => {
+            set_beneficiary::handler(program_id, accounts, beneficiary)
+        }
+        RegistryInstruction::CreateCapabilityRegistry => {
+            create_capability_registry::handler(program_id, accounts)
+        }
+        RegistryInstruction::SetCapability {
+            capability_id,
+            capability_fee_bps,
+            label_hash,
+        } => set_capability::handler(
+            program_id,
+            accounts,
+            capability_id,
+            capability_fee_bps,
+            label_hash,
+        ),
+        RegistryInstruction::MigrateAccount => migrate_account::handler(program_id, accounts),
     };
 
     result?;
 
-    info!("process-instruction success");
+    trace!("process-instruction success");
 
     Ok(())
 }
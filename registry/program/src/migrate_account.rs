@@ -0,0 +1,101 @@
+use crate::emit;
+use serum_common::pack::{Pack, Versioned, DISCRIMINATOR_LEN};
+use serum_registry::accounts::{entity, member, registrar, Entity, Member, Registrar};
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: migrate_account");
+
+    let acc_infos = &mut accounts.iter();
+
+    let acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        acc_info,
+    })?;
+
+    let version = state_transition(StateTransitionRequest { acc_info })?;
+
+    emit(RegistryEvent::AccountMigrated {
+        account: *acc_info.key,
+        version,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: migrate_account");
+
+    let AccessControlRequest {
+        program_id,
+        acc_info,
+    } = req;
+
+    if acc_info.owner != program_id {
+        return Err(RegistryErrorCode::InvalidAccountOwner)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<u8, RegistryError> {
+    trace!("state-transition: migrate_account");
+
+    let StateTransitionRequest { acc_info } = req;
+
+    let data: &mut [u8] = &mut acc_info.try_borrow_mut_data()?;
+    if data.len() < DISCRIMINATOR_LEN {
+        return Err(RegistryErrorCode::WrongSerialization)?;
+    }
+    let mut discriminator = [0; DISCRIMINATOR_LEN];
+    discriminator.copy_from_slice(&data[..DISCRIMINATOR_LEN]);
+
+    let version = if discriminator == registrar::DISCRIMINATOR {
+        migrate(data, |r: &Registrar| r.initialized)?
+    } else if discriminator == entity::DISCRIMINATOR {
+        migrate(data, |e: &Entity| e.initialized)?
+    } else if discriminator == member::DISCRIMINATOR {
+        migrate(data, |m: &Member| m.initialized)?
+    } else if discriminator == [0; DISCRIMINATOR_LEN] {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    } else {
+        return Err(RegistryErrorCode::WrongSerialization)?;
+    };
+
+    trace!("state-transition: success");
+
+    Ok(version)
+}
+
+// Unpacking upgrades the account and packing writes it back with the current
+// layout, so migrating is an unpack followed by a pack.
+fn migrate<T: Pack + Versioned>(
+    data: &mut [u8],
+    initialized: impl Fn(&T) -> bool,
+) -> Result<u8, RegistryError> {
+    let account = T::unpack(data)?;
+    if !initialized(&account) {
+        return Err(RegistryErrorCode::NotInitialized)?;
+    }
+    T::pack(account, data)?;
+    Ok(T::VERSION)
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    acc_info: &'a AccountInfo<'a>,
+}
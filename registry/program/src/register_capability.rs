@@ -1,8 +1,10 @@
+use crate::access_control;
+use crate::emit;
 use serum_common::pack::Pack;
-use serum_registry::accounts::Registrar;
+use serum_registry::accounts::{registrar, Registrar};
 use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
@@ -11,7 +13,7 @@ pub fn handler<'a>(
     capability_id: u8,
     capability_fee_bps: u32,
 ) -> Result<(), RegistryError> {
-    info!("handler: register_capability");
+    trace!("handler: register_capability");
 
     let acc_infos = &mut accounts.iter();
 
@@ -19,9 +21,11 @@ pub fn handler<'a>(
     let registrar_acc_info = next_account_info(acc_infos)?;
 
     access_control(AccessControlRequest {
+        program_id,
         registrar_authority_acc_info,
         registrar_acc_info,
         capability_id,
+        capability_fee_bps,
     })?;
 
     Registrar::unpack_mut(
@@ -36,45 +40,69 @@ pub fn handler<'a>(
         },
     )?;
 
+    emit(RegistryEvent::CapabilityRegistered {
+        registrar: *registrar_acc_info.key,
+        capability_id,
+        fee_bps: capability_fee_bps,
+    })?;
+
     Ok(())
 }
 
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
-    info!("access-control: register_capability");
+    trace!("access-control: register_capability");
 
     let AccessControlRequest {
+        program_id,
         registrar_authority_acc_info,
         registrar_acc_info,
         capability_id,
+        capability_fee_bps,
     } = req;
 
-    // todo
+    let registrar =
+        access_control::governance(program_id, registrar_acc_info, registrar_authority_acc_info)?;
+    if registrar.capability_registry != Pubkey::default() {
+        return Err(RegistryErrorCode::CapabilitiesMigrated)?;
+    }
+
+    if !registrar::is_valid_capability_id(capability_id) {
+        return Err(RegistryErrorCode::InvalidCapabilityId)?;
+    }
+    if capability_fee_bps > registrar::MAX_CAPABILITY_FEE_BPS {
+        return Err(RegistryErrorCode::InvalidCapabilityFee)?;
+    }
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
-    info!("state-transition: register_capability");
+    trace!("state-transition: register_capability");
 
     let StateTransitionRequest {
-        mut registrar,
+        registrar,
         capability_id,
         capability_fee_bps,
     } = req;
 
-    registrar.capabilities_fees_bps[capability_id as usize] = capability_fee_bps;
+    let fee_bps = registrar
+        .capability_fee_bps_mut(capability_id)
+        .ok_or(RegistryErrorCode::InvalidCapabilityId)?;
+    *fee_bps = capability_fee_bps;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
 
 struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
     registrar_authority_acc_info: &'a AccountInfo<'a>,
     registrar_acc_info: &'a AccountInfo<'a>,
     capability_id: u8,
+    capability_fee_bps: u32,
 }
 
 struct StateTransitionRequest<'a> {
@@ -0,0 +1,125 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Member;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    beneficiary: Pubkey,
+) -> Result<(), RegistryError> {
+    trace!("handler: set_beneficiary");
+
+    let acc_infos = &mut accounts.iter();
+
+    let member_acc_info = next_account_info(acc_infos)?;
+    let beneficiary_acc_info = next_account_info(acc_infos)?;
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        entity_acc_info,
+        registrar_acc_info,
+        beneficiary,
+    })?;
+
+    Member::unpack_mut(
+        &mut member_acc_info.try_borrow_mut_data()?,
+        &mut |member: &mut Member| {
+            state_transition(StateTransitionRequest {
+                member,
+                beneficiary,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::BeneficiarySet {
+        member: *member_acc_info.key,
+        beneficiary,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: set_beneficiary");
+
+    let AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        entity_acc_info,
+        registrar_acc_info,
+        beneficiary,
+    } = req;
+
+    if !beneficiary_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    let member = access_control::member(member_acc_info, program_id)?;
+    if !member.authorities.is_beneficiary(beneficiary_acc_info.key) {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    // Nobody could ever sign for the default key.
+    if beneficiary == Pubkey::default() {
+        return Err(RegistryErrorCode::InvalidBeneficiary)?;
+    }
+
+    if member.entity != *entity_acc_info.key {
+        return Err(RegistryErrorCode::MemberEntityMismatch)?;
+    }
+    let entity = access_control::entity(entity_acc_info, program_id)?;
+    if entity.registrar != *registrar_acc_info.key {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.paused {
+        return Err(RegistryErrorCode::Paused)?;
+    }
+    // Rotating to a blocked key would sidestep the blocklist checked on
+    // join_entity.
+    if registrar.is_blocked(&beneficiary) {
+        return Err(RegistryErrorCode::BeneficiaryBlocked)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: set_beneficiary");
+
+    let StateTransitionRequest {
+        member,
+        beneficiary,
+    } = req;
+
+    member.authorities.beneficiary = beneficiary;
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    member_acc_info: &'a AccountInfo<'a>,
+    beneficiary_acc_info: &'a AccountInfo<'a>,
+    entity_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+    beneficiary: Pubkey,
+}
+
+struct StateTransitionRequest<'a> {
+    member: &'a mut Member,
+    beneficiary: Pubkey,
+}
@@ -0,0 +1,127 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::{capability_registry, registrar};
+use serum_registry::accounts::{CapabilityDescriptor, CapabilityRegistry};
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    capability_id: u8,
+    capability_fee_bps: u32,
+    label_hash: [u8; 32],
+) -> Result<(), RegistryError> {
+    trace!("handler: set_capability");
+
+    let acc_infos = &mut accounts.iter();
+
+    let registrar_authority_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+    let capability_registry_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        capability_id,
+        capability_fee_bps,
+    })?;
+
+    CapabilityRegistry::unpack_mut(
+        &mut capability_registry_acc_info.try_borrow_mut_data()?,
+        &mut |capability_registry: &mut CapabilityRegistry| {
+            state_transition(StateTransitionRequest {
+                capability_registry,
+                capability_id,
+                capability_fee_bps,
+                label_hash,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::CapabilityRegistered {
+        registrar: *registrar_acc_info.key,
+        capability_id,
+        fee_bps: capability_fee_bps,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: set_capability");
+
+    let AccessControlRequest {
+        program_id,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        capability_id,
+        capability_fee_bps,
+    } = req;
+
+    let registrar =
+        access_control::governance(program_id, registrar_acc_info, registrar_authority_acc_info)?;
+    let _ = access_control::capability_registry(
+        capability_registry_acc_info,
+        registrar_acc_info,
+        &registrar,
+        program_id,
+    )?;
+
+    if !capability_registry::is_valid_capability_id(capability_id) {
+        return Err(RegistryErrorCode::InvalidCapabilityId)?;
+    }
+    if capability_fee_bps > registrar::MAX_CAPABILITY_FEE_BPS {
+        return Err(RegistryErrorCode::InvalidCapabilityFee)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: set_capability");
+
+    let StateTransitionRequest {
+        capability_registry,
+        capability_id,
+        capability_fee_bps,
+        label_hash,
+    } = req;
+
+    if !capability_registry.set(CapabilityDescriptor {
+        id: capability_id,
+        fee_bps: capability_fee_bps,
+        label_hash,
+    }) {
+        return Err(RegistryErrorCode::InvalidCapabilityId)?;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    registrar_authority_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+    capability_registry_acc_info: &'a AccountInfo<'a>,
+    capability_id: u8,
+    capability_fee_bps: u32,
+}
+
+struct StateTransitionRequest<'a> {
+    capability_registry: &'a mut CapabilityRegistry,
+    capability_id: u8,
+    capability_fee_bps: u32,
+    label_hash: [u8; 32],
+}
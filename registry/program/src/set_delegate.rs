@@ -0,0 +1,110 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Member;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+/// Sets the member's delegate. The default key revokes it.
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    delegate: Pubkey,
+) -> Result<(), RegistryError> {
+    trace!("handler: set_delegate");
+
+    let acc_infos = &mut accounts.iter();
+
+    let member_acc_info = next_account_info(acc_infos)?;
+    let beneficiary_acc_info = next_account_info(acc_infos)?;
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        entity_acc_info,
+        registrar_acc_info,
+    })?;
+
+    Member::unpack_mut(
+        &mut member_acc_info.try_borrow_mut_data()?,
+        &mut |member: &mut Member| {
+            state_transition(StateTransitionRequest { member, delegate }).map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::DelegateSet {
+        member: *member_acc_info.key,
+        delegate,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: set_delegate");
+
+    let AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        entity_acc_info,
+        registrar_acc_info,
+    } = req;
+
+    if !beneficiary_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    let member = access_control::member(member_acc_info, program_id)?;
+    if !member.authorities.is_beneficiary(beneficiary_acc_info.key) {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    if member.amount != 0 || member.mega_amount != 0 {
+        return Err(RegistryErrorCode::MemberNotEmpty)?;
+    }
+
+    if member.entity != *entity_acc_info.key {
+        return Err(RegistryErrorCode::MemberEntityMismatch)?;
+    }
+    let entity = access_control::entity(entity_acc_info, program_id)?;
+    if entity.registrar != *registrar_acc_info.key {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.paused {
+        return Err(RegistryErrorCode::Paused)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: set_delegate");
+
+    let StateTransitionRequest { member, delegate } = req;
+
+    member.authorities.delegate = delegate;
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    member_acc_info: &'a AccountInfo<'a>,
+    beneficiary_acc_info: &'a AccountInfo<'a>,
+    entity_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    member: &'a mut Member,
+    delegate: Pubkey,
+}
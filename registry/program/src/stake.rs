@@ -1,8 +1,8 @@
+use crate::access_control;
 use serum_common::pack::Pack;
 use serum_registry::accounts::{Entity, Member};
 use serum_registry::error::{RegistryError, RegistryErrorCode};
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
@@ -11,7 +11,7 @@ pub fn handler<'a>(
     amount: u64,
     is_mega: bool,
 ) -> Result<(), RegistryError> {
-    info!("handler: stake");
+    trace!("handler: stake");
 
     let acc_infos = &mut accounts.iter();
 
@@ -59,7 +59,7 @@ pub fn handler<'a>(
 }
 
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
-    info!("access-control: stake");
+    trace!("access-control: stake");
 
     let AccessControlRequest {
         depositor_tok_owner_acc_info,
@@ -70,15 +70,17 @@ fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
         token_program_acc_info,
     } = req;
 
+    access_control::token_program(token_program_acc_info)?;
+
     // todo
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
-    info!("state-transition: stake");
+    trace!("state-transition: stake");
 
     let StateTransitionRequest {
         entity,
@@ -98,25 +100,19 @@ fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
         // todo
     }
 
+    let (amount, mega_amount) = if is_mega { (0, amount) } else { (amount, 0) };
+
     // Member account.
     {
-        if is_mega {
-            member.mega_amount += amount;
-        } else {
-            member.amount = amount;
-        }
+        member.add_stake(amount, mega_amount)?;
     }
 
     // Entity.
     {
-        if is_mega {
-            entity.mega_amount += amount;
-        } else {
-            entity.amount += amount;
-        }
+        entity.add_stake(amount, mega_amount)?;
     }
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
@@ -1,13 +1,12 @@
 use serum_registry::error::{RegistryError, RegistryErrorCode};
 use solana_sdk::account_info::AccountInfo;
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
 ) -> Result<(), RegistryError> {
-    info!("handler: initiate_stake_withdrawal");
+    trace!("handler: initiate_stake_withdrawal");
 
     access_control(AccessControlRequest {})?;
 
@@ -16,11 +15,11 @@ pub fn handler<'a>(
 
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
     // todo
-    info!("access-control: initiate_stake_withdrawal");
+    trace!("access-control: initiate_stake_withdrawal");
 
     let AccessControlRequest {} = req;
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
@@ -28,11 +27,11 @@ fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
     // todo
 
-    info!("state-transition: initiate_stake_withdrawal");
+    trace!("state-transition: initiate_stake_withdrawal");
 
     let StateTransitionRequest {} = req;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
@@ -0,0 +1,155 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::{Entity, Member};
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> Result<(), RegistryError> {
+    trace!("handler: switch_entity");
+
+    let acc_infos = &mut accounts.iter();
+
+    let member_acc_info = next_account_info(acc_infos)?;
+    let beneficiary_acc_info = next_account_info(acc_infos)?;
+    let curr_entity_acc_info = next_account_info(acc_infos)?;
+    let new_entity_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        curr_entity_acc_info,
+        new_entity_acc_info,
+        registrar_acc_info,
+    })?;
+
+    Entity::unpack_mut(
+        &mut curr_entity_acc_info.try_borrow_mut_data()?,
+        &mut |curr_entity: &mut Entity| {
+            Entity::unpack_mut(
+                &mut new_entity_acc_info.try_borrow_mut_data()?,
+                &mut |new_entity: &mut Entity| {
+                    Member::unpack_mut(
+                        &mut member_acc_info.try_borrow_mut_data()?,
+                        &mut |member: &mut Member| {
+                            state_transition(StateTransitionRequest {
+                                member,
+                                curr_entity,
+                                new_entity,
+                                new_entity_acc_info,
+                            })
+                            .map_err(Into::into)
+                        },
+                    )
+                },
+            )
+        },
+    )?;
+
+    emit(RegistryEvent::MemberSwitched {
+        member: *member_acc_info.key,
+        from: *curr_entity_acc_info.key,
+        to: *new_entity_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: switch_entity");
+
+    let AccessControlRequest {
+        program_id,
+        member_acc_info,
+        beneficiary_acc_info,
+        curr_entity_acc_info,
+        new_entity_acc_info,
+        registrar_acc_info,
+    } = req;
+
+    if !beneficiary_acc_info.is_signer {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    let member = access_control::member(member_acc_info, program_id)?;
+    if !member.authorities.is_beneficiary(beneficiary_acc_info.key) {
+        return Err(RegistryErrorCode::Unauthorized)?;
+    }
+    if member.entity != *curr_entity_acc_info.key {
+        return Err(RegistryErrorCode::MemberEntityMismatch)?;
+    }
+    if curr_entity_acc_info.key == new_entity_acc_info.key {
+        return Err(RegistryErrorCode::SameEntity)?;
+    }
+
+    let curr_entity = access_control::entity(curr_entity_acc_info, program_id)?;
+    let new_entity = access_control::entity(new_entity_acc_info, program_id)?;
+    if curr_entity.registrar != *registrar_acc_info.key
+        || new_entity.registrar != *registrar_acc_info.key
+    {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+
+    // Moving into an entity is subject to the same rules as joining one.
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.paused {
+        return Err(RegistryErrorCode::Paused)?;
+    }
+    if registrar.is_blocked(&member.authorities.beneficiary) {
+        return Err(RegistryErrorCode::BeneficiaryBlocked)?;
+    }
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: switch_entity");
+
+    let StateTransitionRequest {
+        member,
+        curr_entity,
+        new_entity,
+        new_entity_acc_info,
+    } = req;
+
+    // Entity.
+    {
+        curr_entity.sub_stake(member.amount, member.mega_amount)?;
+        curr_entity.remove_member()?;
+        new_entity.add_stake(member.amount, member.mega_amount)?;
+        new_entity.add_member()?;
+    }
+
+    // Member account.
+    {
+        member.entity = *new_entity_acc_info.key;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    member_acc_info: &'a AccountInfo<'a>,
+    beneficiary_acc_info: &'a AccountInfo<'a>,
+    curr_entity_acc_info: &'a AccountInfo<'a>,
+    new_entity_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a, 'b> {
+    member: &'b mut Member,
+    curr_entity: &'b mut Entity,
+    new_entity: &'b mut Entity,
+    new_entity_acc_info: &'a AccountInfo<'a>,
+}
@@ -1,26 +1,38 @@
+use crate::access_control;
+use crate::emit;
 use serum_common::pack::Pack;
 use serum_registry::accounts::Entity;
 use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
 use solana_sdk::account_info::{next_account_info, AccountInfo};
-use solana_sdk::info;
 use solana_sdk::pubkey::Pubkey;
 
 pub fn handler<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
     leader: Pubkey,
-    capabilities: u32,
+    capabilities: u64,
 ) -> Result<(), RegistryError> {
-    info!("handler: update_entity");
+    trace!("handler: update_entity");
 
     let acc_infos = &mut accounts.iter();
 
     let entity_acc_info = next_account_info(acc_infos)?;
     let entity_leader_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+    let capability_registry_acc_info =
+        access_control::next_capability_registry(registrar_acc_info, acc_infos)?;
+    let multisig_signer_acc_infos = acc_infos.as_slice();
 
     access_control(AccessControlRequest {
+        program_id,
         entity_acc_info,
         entity_leader_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        multisig_signer_acc_infos,
+        leader,
+        capabilities,
     })?;
 
     Entity::unpack_mut(
@@ -35,26 +47,60 @@ pub fn handler<'a>(
         },
     )?;
 
+    emit(RegistryEvent::EntityUpdated {
+        entity: *entity_acc_info.key,
+        leader,
+        capabilities,
+    })?;
+
     Ok(())
 }
 
 fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
-    info!("access-control: update_entity");
+    trace!("access-control: update_entity");
 
     let AccessControlRequest {
+        program_id,
         entity_acc_info,
         entity_leader_acc_info,
+        registrar_acc_info,
+        capability_registry_acc_info,
+        multisig_signer_acc_infos,
+        leader,
+        capabilities,
     } = req;
 
-    // todo
+    let entity = access_control::entity_leader_multisig(
+        program_id,
+        entity_acc_info,
+        entity_leader_acc_info,
+        multisig_signer_acc_infos,
+    )?;
+    if entity.registrar != *registrar_acc_info.key {
+        return Err(RegistryErrorCode::EntityRegistrarMismatch)?;
+    }
+
+    // Same policy as CreateEntity, so it can't be sidestepped by handing
+    // the entity over afterwards.
+    let registrar = access_control::registrar(registrar_acc_info, program_id)?;
+    if registrar.separate_leader_and_authority && registrar.authority == leader {
+        return Err(RegistryErrorCode::LeaderIsRegistrarAuthority)?;
+    }
+    access_control::capabilities(
+        program_id,
+        capabilities,
+        registrar_acc_info,
+        &registrar,
+        capability_registry_acc_info,
+    )?;
 
-    info!("access-control: success");
+    trace!("access-control: success");
 
     Ok(())
 }
 
 fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
-    info!("state-transition: update_entity");
+    trace!("state-transition: update_entity");
 
     let StateTransitionRequest {
         entity,
@@ -65,18 +111,24 @@ fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
     entity.leader = leader;
     entity.capabilities = capabilities;
 
-    info!("state-transition: success");
+    trace!("state-transition: success");
 
     Ok(())
 }
 
 struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
     entity_acc_info: &'a AccountInfo<'a>,
     entity_leader_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+    capability_registry_acc_info: Option<&'a AccountInfo<'a>>,
+    multisig_signer_acc_infos: &'a [AccountInfo<'a>],
+    leader: Pubkey,
+    capabilities: u64,
 }
 
 struct StateTransitionRequest<'a> {
     entity: &'a mut Entity,
-    capabilities: u32,
+    capabilities: u64,
     leader: Pubkey,
 }
@@ -0,0 +1,121 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::EntityMetadata;
+use serum_registry::error::{RegistryError, RegistryErrorCode};
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    name: Option<String>,
+    url: Option<String>,
+    description: Option<String>,
+) -> Result<(), RegistryError> {
+    trace!("handler: update_metadata");
+
+    let acc_infos = &mut accounts.iter();
+
+    let metadata_acc_info = next_account_info(acc_infos)?;
+    let entity_leader_acc_info = next_account_info(acc_infos)?;
+    let entity_acc_info = next_account_info(acc_infos)?;
+    let multisig_signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        metadata_acc_info,
+        entity_leader_acc_info,
+        entity_acc_info,
+    })?;
+
+    EntityMetadata::unpack_mut(
+        &mut metadata_acc_info.try_borrow_mut_data()?,
+        &mut |metadata: &mut EntityMetadata| {
+            state_transition(StateTransitionRequest {
+                metadata,
+                name: name.clone(),
+                url: url.clone(),
+                description: description.clone(),
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::MetadataUpdated {
+        entity: *entity_acc_info.key,
+        metadata: *metadata_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: update_metadata");
+
+    let AccessControlRequest {
+        program_id,
+        multisig_signer_acc_infos,
+        metadata_acc_info,
+        entity_leader_acc_info,
+        entity_acc_info,
+    } = req;
+
+    let _ = access_control::entity_leader_multisig(
+        program_id,
+        entity_acc_info,
+        entity_leader_acc_info,
+        multisig_signer_acc_infos,
+    )?;
+    let _ = access_control::entity_metadata(metadata_acc_info, entity_acc_info, program_id)?;
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: update_metadata");
+
+    let StateTransitionRequest {
+        metadata,
+        name,
+        url,
+        description,
+    } = req;
+
+    if let Some(name) = name {
+        metadata.name = name;
+    }
+    if let Some(url) = url {
+        metadata.url = url;
+    }
+    if let Some(description) = description {
+        metadata.description = description;
+    }
+
+    if !metadata.is_valid() {
+        return Err(RegistryErrorCode::InvalidMetadata)?;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    multisig_signer_acc_infos: &'a [AccountInfo<'a>],
+    metadata_acc_info: &'a AccountInfo<'a>,
+    entity_leader_acc_info: &'a AccountInfo<'a>,
+    entity_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    metadata: &'a mut EntityMetadata,
+    name: Option<String>,
+    url: Option<String>,
+    description: Option<String>,
+}
@@ -0,0 +1,103 @@
+use crate::access_control;
+use crate::emit;
+use serum_common::pack::Pack;
+use serum_registry::accounts::Registrar;
+use serum_registry::error::RegistryError;
+use serum_registry::events::RegistryEvent;
+use solana_sdk::account_info::{next_account_info, AccountInfo};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn handler<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    withdrawal_timelock: Option<u64>,
+    separate_leader_and_authority: Option<bool>,
+    heartbeat_timeout: Option<u64>,
+) -> Result<(), RegistryError> {
+    trace!("handler: update_registrar");
+
+    let acc_infos = &mut accounts.iter();
+
+    let registrar_authority_acc_info = next_account_info(acc_infos)?;
+    let registrar_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+    })?;
+
+    Registrar::unpack_mut(
+        &mut registrar_acc_info.try_borrow_mut_data()?,
+        &mut |registrar: &mut Registrar| {
+            state_transition(StateTransitionRequest {
+                registrar,
+                withdrawal_timelock,
+                separate_leader_and_authority,
+                heartbeat_timeout,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    emit(RegistryEvent::RegistrarUpdated {
+        registrar: *registrar_acc_info.key,
+    })?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), RegistryError> {
+    trace!("access-control: update_registrar");
+
+    let AccessControlRequest {
+        program_id,
+        registrar_authority_acc_info,
+        registrar_acc_info,
+    } = req;
+
+    let _ =
+        access_control::governance(program_id, registrar_acc_info, registrar_authority_acc_info)?;
+
+    trace!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransitionRequest) -> Result<(), RegistryError> {
+    trace!("state-transition: update_registrar");
+
+    let StateTransitionRequest {
+        registrar,
+        withdrawal_timelock,
+        separate_leader_and_authority,
+        heartbeat_timeout,
+    } = req;
+
+    if let Some(withdrawal_timelock) = withdrawal_timelock {
+        registrar.withdrawal_timelock = withdrawal_timelock;
+    }
+    if let Some(separate_leader_and_authority) = separate_leader_and_authority {
+        registrar.separate_leader_and_authority = separate_leader_and_authority;
+    }
+    if let Some(heartbeat_timeout) = heartbeat_timeout {
+        registrar.heartbeat_timeout = heartbeat_timeout;
+    }
+
+    trace!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a> {
+    program_id: &'a Pubkey,
+    registrar_authority_acc_info: &'a AccountInfo<'a>,
+    registrar_acc_info: &'a AccountInfo<'a>,
+}
+
+struct StateTransitionRequest<'a> {
+    registrar: &'a mut Registrar,
+    withdrawal_timelock: Option<u64>,
+    separate_leader_and_authority: Option<bool>,
+    heartbeat_timeout: Option<u64>,
+}
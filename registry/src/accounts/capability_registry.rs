@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use serum_common::pack::*;
+use solana_client_gen::solana_sdk::pubkey::Pubkey;
+
+/// Number of capabilities a CapabilityRegistry can describe. Valid
+/// capability ids are `0..MAX_CAPABILITIES`, one per bit of an entity's
+/// capabilities.
+pub const MAX_CAPABILITIES: usize = 64;
+
+/// Size of the account holding a `CapabilityRegistry`, i.e., the size of the
+/// serialization when every capability is described. The list is prefixed
+/// with its u64 length.
+pub const SIZE: u64 = (DISCRIMINATOR_LEN + 1 + 32 + 8 + MAX_CAPABILITIES * DESCRIPTOR_SIZE) as u64;
+
+const DESCRIPTOR_SIZE: usize = 1 + 4 + 32;
+
+/// Prefix identifying CapabilityRegistry accounts.
+pub const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"srm:caps";
+
+/// CapabilityRegistry describes the capabilities of a registrar, replacing
+/// the registrar's fixed fee table once created.
+///
+/// Like EntityMetadata, the serialization is variable length. The account is
+/// allocated with `SIZE` bytes and the list grows into the zeroed tail as
+/// capabilities are registered.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityRegistry {
+    /// Set by the program on creation.
+    pub initialized: bool,
+    /// The registrar whose capabilities are described.
+    pub registrar: Pubkey,
+    /// Registered capabilities, in the order they were first registered.
+    pub capabilities: Vec<CapabilityDescriptor>,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityDescriptor {
+    /// Bit of `Entity::capabilities` representing the capability.
+    pub id: u8,
+    /// Capability fee in bps.
+    pub fee_bps: u32,
+    /// Hash of a human readable label, kept off chain.
+    pub label_hash: [u8; 32],
+}
+
+/// Returns true if the capability id has a bit in an entity's capabilities.
+pub fn is_valid_capability_id(capability_id: u8) -> bool {
+    usize::from(capability_id) < MAX_CAPABILITIES
+}
+
+impl CapabilityRegistry {
+    /// Returns the descriptor of the given capability, if registered.
+    pub fn get(&self, capability_id: u8) -> Option<&CapabilityDescriptor> {
+        self.capabilities.iter().find(|c| c.id == capability_id)
+    }
+
+    /// Registers the capability, replacing any previous descriptor with the
+    /// same id. Returns false, leaving the registry untouched, if the id is
+    /// invalid.
+    pub fn set(&mut self, descriptor: CapabilityDescriptor) -> bool {
+        if !is_valid_capability_id(descriptor.id) {
+            return false;
+        }
+        match self.capabilities.iter_mut().find(|c| c.id == descriptor.id) {
+            Some(c) => *c = descriptor,
+            None => self.capabilities.push(descriptor),
+        }
+        true
+    }
+}
+
+impl Pack for CapabilityRegistry {
+    fn pack(src: CapabilityRegistry, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if src.size()? > dst.len() as u64 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        serum_common::pack::into_bytes_with_discriminator(&DISCRIMINATOR, &src, dst)
+    }
+
+    fn unpack_unchecked(src: &mut &[u8]) -> Result<CapabilityRegistry, ProgramError> {
+        serum_common::pack::from_reader_with_discriminator(&DISCRIMINATOR, src)
+    }
+
+    fn size(&self) -> Result<u64, ProgramError> {
+        serum_common::pack::bytes_size(&self).map(|size| size + DISCRIMINATOR_LEN as u64)
+    }
+
+    // The account is larger than the serialization, so trailing bytes are
+    // expected.
+    fn unpack(src: &[u8]) -> Result<CapabilityRegistry, ProgramError> {
+        let mut src_mut = src;
+        CapabilityRegistry::unpack_unchecked(&mut src_mut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_size() {
+        let mut registry = CapabilityRegistry {
+            initialized: true,
+            registrar: Pubkey::new_rand(),
+            capabilities: vec![],
+        };
+        for id in 0..MAX_CAPABILITIES as u8 {
+            assert!(registry.set(CapabilityDescriptor {
+                id,
+                fee_bps: 10_000,
+                label_hash: [id; 32],
+            }));
+        }
+        assert_eq!(registry.size().unwrap(), SIZE);
+    }
+
+    #[test]
+    fn set_and_get() {
+        let mut data = vec![0u8; SIZE as usize];
+        CapabilityRegistry::unpack_mut(&mut data, &mut |r: &mut CapabilityRegistry| {
+            assert!(r.capabilities.is_empty());
+            let d = CapabilityDescriptor {
+                id: 40,
+                fee_bps: 5,
+                label_hash: [1; 32],
+            };
+            assert!(r.set(d));
+            assert!(r.set(CapabilityDescriptor { fee_bps: 7, ..d }));
+            assert!(!r.set(CapabilityDescriptor {
+                id: MAX_CAPABILITIES as u8,
+                ..d
+            }));
+            Ok(())
+        })
+        .unwrap();
+
+        let registry = CapabilityRegistry::unpack(&data).unwrap();
+        assert_eq!(registry.capabilities.len(), 1);
+        assert_eq!(registry.get(40).unwrap().fee_bps, 7);
+        assert_eq!(registry.get(40).unwrap().label_hash, [1; 32]);
+        assert!(registry.get(0).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::RegistryErrorCode;
 use num_enum::IntoPrimitive;
 use serde::{Deserialize, Serialize};
 use serum_common::pack::*;
@@ -7,7 +8,8 @@ use solana_client_gen::solana_sdk::pubkey::Pubkey;
 lazy_static::lazy_static! {
     pub static ref SIZE: u64 = Entity::default()
                 .size()
-                .expect("Vesting has a fixed size");
+                .expect("Vesting has a fixed size")
+                + RESERVED_LEN as u64;
 }
 
 /// Entity is the account representing a single "node" that addresses can
@@ -16,6 +18,8 @@ lazy_static::lazy_static! {
 pub struct Entity {
     /// Set when this entity is registered with the program.
     pub initialized: bool,
+    /// The registrar the entity was created with.
+    pub registrar: Pubkey,
     /// Leader of the entity, i.e., the one responsible for fulfilling node
     /// duties.
     pub leader: Pubkey,
@@ -23,11 +27,77 @@ pub struct Entity {
     pub amount: u64,
     /// Amount of the mega token staked to this entity.
     pub mega_amount: u64,
-    /// Bitmap representing this entity's capabilities .
-    pub capabilities: u32,
+    /// Bitmap representing this entity's capabilities, one bit per
+    /// capability id of the registrar.
+    pub capabilities: u64,
     /// Type of stake backing this entity (determines voting rights)
     /// of the stakers.
     pub stake_kind: StakeKind,
+    /// Slot of the leader's most recent heartbeat, attesting it's fulfilling
+    /// node duties. Zero if the leader never sent one.
+    pub last_heartbeat_slot: u64,
+    /// Number of Member accounts currently belonging to this entity. The
+    /// entity can't be closed until this is zero.
+    pub members: u32,
+}
+
+impl Entity {
+    /// Credits the given stake to the entity, leaving it untouched on
+    /// overflow.
+    pub fn add_stake(&mut self, amount: u64, mega_amount: u64) -> Result<(), RegistryErrorCode> {
+        let new_amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(RegistryErrorCode::Overflow)?;
+        let new_mega_amount = self
+            .mega_amount
+            .checked_add(mega_amount)
+            .ok_or(RegistryErrorCode::Overflow)?;
+        self.amount = new_amount;
+        self.mega_amount = new_mega_amount;
+        Ok(())
+    }
+
+    /// Debits the given stake from the entity, leaving it untouched if
+    /// either balance is insufficient.
+    pub fn sub_stake(&mut self, amount: u64, mega_amount: u64) -> Result<(), RegistryErrorCode> {
+        let new_amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(RegistryErrorCode::InsufficientBalance)?;
+        let new_mega_amount = self
+            .mega_amount
+            .checked_sub(mega_amount)
+            .ok_or(RegistryErrorCode::InsufficientBalance)?;
+        self.amount = new_amount;
+        self.mega_amount = new_mega_amount;
+        Ok(())
+    }
+
+    /// Counts a new member of the entity.
+    pub fn add_member(&mut self) -> Result<(), RegistryErrorCode> {
+        self.members = self
+            .members
+            .checked_add(1)
+            .ok_or(RegistryErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Returns true if the leader hasn't sent a heartbeat within `timeout`
+    /// slots of `current_slot`. An entity that never sent one is stale. A
+    /// zero timeout disables the check.
+    pub fn is_stale(&self, current_slot: u64, timeout: u64) -> bool {
+        timeout != 0 && current_slot.saturating_sub(self.last_heartbeat_slot) > timeout
+    }
+
+    /// Stops counting a member that left the entity.
+    pub fn remove_member(&mut self) -> Result<(), RegistryErrorCode> {
+        self.members = self
+            .members
+            .checked_sub(1)
+            .ok_or(RegistryErrorCode::Underflow)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq, IntoPrimitive, Clone, Copy, Serialize, Deserialize)]
@@ -43,4 +113,79 @@ impl Default for StakeKind {
     }
 }
 
-serum_common::packable!(Entity);
+/// Prefix identifying Entity accounts.
+pub const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"srm:enty";
+
+serum_common::versioned_packable!(Entity, DISCRIMINATOR);
+
+impl Versioned for Entity {
+    const VERSION: u8 = 1;
+
+    fn upgrade(_version: u8, _src: &mut &[u8]) -> Result<Self, ProgramError> {
+        // No older layouts.
+        Err(ProgramError::InvalidAccountData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_stake() {
+        let mut entity = Entity::default();
+        entity.add_stake(1, 2).unwrap();
+        entity.add_stake(u64::MAX - 1, 0).unwrap();
+        assert_eq!((entity.amount, entity.mega_amount), (u64::MAX, 2));
+
+        // Nothing changes if either balance would overflow.
+        assert!(entity.add_stake(0, u64::MAX).is_err());
+        assert!(entity.add_stake(1, 0).is_err());
+        assert_eq!((entity.amount, entity.mega_amount), (u64::MAX, 2));
+    }
+
+    #[test]
+    fn sub_stake() {
+        let mut entity = Entity::default();
+        entity.add_stake(3, 2).unwrap();
+        entity.sub_stake(3, 1).unwrap();
+        assert_eq!((entity.amount, entity.mega_amount), (0, 1));
+
+        // Nothing changes if either balance is insufficient.
+        assert!(entity.sub_stake(0, 2).is_err());
+        assert!(entity.sub_stake(1, 1).is_err());
+        assert_eq!((entity.amount, entity.mega_amount), (0, 1));
+    }
+
+    #[test]
+    fn is_stale() {
+        let mut entity = Entity::default();
+        assert!(!entity.is_stale(100, 0));
+        assert!(entity.is_stale(100, 10));
+
+        entity.last_heartbeat_slot = 95;
+        assert!(!entity.is_stale(100, 10));
+        assert!(!entity.is_stale(105, 10));
+        assert!(entity.is_stale(106, 10));
+    }
+
+    #[test]
+    fn members() {
+        let mut entity = Entity::default();
+        assert!(matches!(
+            entity.remove_member(),
+            Err(RegistryErrorCode::Underflow)
+        ));
+        entity.add_member().unwrap();
+        entity.add_member().unwrap();
+        entity.remove_member().unwrap();
+        assert_eq!(entity.members, 1);
+
+        entity.members = u32::MAX;
+        assert!(matches!(
+            entity.add_member(),
+            Err(RegistryErrorCode::Overflow)
+        ));
+        assert_eq!(entity.members, u32::MAX);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::RegistryErrorCode;
 use serum_common::pack::*;
 use solana_client_gen::solana_sdk::pubkey::Pubkey;
 
@@ -5,7 +6,8 @@ use solana_client_gen::solana_sdk::pubkey::Pubkey;
 lazy_static::lazy_static! {
     pub static ref SIZE: u64 = Member::default()
                 .size()
-                .expect("Vesting has a fixed size");
+                .expect("Vesting has a fixed size")
+                + RESERVED_LEN as u64;
 }
 
 /// Member account tracks membership with a node `Entity`.
@@ -15,15 +17,170 @@ pub struct Member {
     pub initialized: bool,
     /// Entity account providing membership.
     pub entity: Pubkey,
-    /// The key that is allowed to redeem assets from the staking pool.
-    pub beneficiary: Pubkey,
-    /// Deleate key authorized to deposit or withdraw from the staking pool
-    /// on behalf of the beneficiary.
-    pub delegate: Pubkey,
+    /// Keys with authority over this account.
+    pub authorities: MemberAuthorities,
     /// Amount of SRM staked.
     pub amount: u64,
     /// Amount of MSRM staked.
     pub mega_amount: u64,
 }
 
-serum_common::packable!(Member);
+impl Member {
+    /// Credits the given stake to the member, leaving it untouched on
+    /// overflow.
+    pub fn add_stake(&mut self, amount: u64, mega_amount: u64) -> Result<(), RegistryErrorCode> {
+        let new_amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(RegistryErrorCode::Overflow)?;
+        let new_mega_amount = self
+            .mega_amount
+            .checked_add(mega_amount)
+            .ok_or(RegistryErrorCode::Overflow)?;
+        self.amount = new_amount;
+        self.mega_amount = new_mega_amount;
+        Ok(())
+    }
+
+    /// Debits the given stake from the member, leaving it untouched if
+    /// either balance is insufficient.
+    pub fn sub_stake(&mut self, amount: u64, mega_amount: u64) -> Result<(), RegistryErrorCode> {
+        let new_amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(RegistryErrorCode::InsufficientBalance)?;
+        let new_mega_amount = self
+            .mega_amount
+            .checked_sub(mega_amount)
+            .ok_or(RegistryErrorCode::InsufficientBalance)?;
+        self.amount = new_amount;
+        self.mega_amount = new_mega_amount;
+        Ok(())
+    }
+}
+
+/// MemberAuthorities groups the keys allowed to act on a `Member` account.
+///
+/// Serialized inline, so the account layout is the same as having the keys
+/// as fields directly on `Member`.
+#[derive(Clone, Default, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MemberAuthorities {
+    /// The key that is allowed to redeem assets from the staking pool.
+    pub beneficiary: Pubkey,
+    /// Deleate key authorized to deposit or withdraw from the staking pool
+    /// on behalf of the beneficiary. Unset if zero.
+    pub delegate: Pubkey,
+}
+
+impl MemberAuthorities {
+    /// Returns the delegate, if one is set.
+    pub fn delegate(&self) -> Option<&Pubkey> {
+        if self.delegate == Pubkey::default() {
+            None
+        } else {
+            Some(&self.delegate)
+        }
+    }
+
+    pub fn is_beneficiary(&self, key: &Pubkey) -> bool {
+        self.beneficiary == *key
+    }
+
+    pub fn is_delegate(&self, key: &Pubkey) -> bool {
+        self.delegate() == Some(key)
+    }
+}
+
+/// Prefix identifying Member accounts.
+pub const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"srm:mmbr";
+
+serum_common::versioned_packable!(Member, DISCRIMINATOR);
+
+impl Versioned for Member {
+    const VERSION: u8 = 1;
+
+    fn upgrade(_version: u8, _src: &mut &[u8]) -> Result<Self, ProgramError> {
+        // No older layouts.
+        Err(ProgramError::InvalidAccountData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_unchanged() {
+        let entity = Pubkey::new_rand();
+        let beneficiary = Pubkey::new_rand();
+        let delegate = Pubkey::new_rand();
+        let member = Member {
+            initialized: true,
+            entity,
+            authorities: MemberAuthorities {
+                beneficiary,
+                delegate,
+            },
+            amount: 1,
+            mega_amount: 2,
+        };
+
+        let mut dst = Vec::new();
+        dst.resize(member.size().unwrap() as usize, 0u8);
+        Member::pack(member, &mut dst).unwrap();
+
+        let mut expected = DISCRIMINATOR.to_vec();
+        expected.push(Member::VERSION);
+        expected.extend(to_bytes(&(true, entity, beneficiary, delegate, 1u64, 2u64)).unwrap());
+        assert_eq!(dst, expected);
+
+        let member = Member::unpack(&dst).unwrap();
+        assert_eq!(member.authorities.beneficiary, beneficiary);
+        assert_eq!(member.authorities.delegate, delegate);
+    }
+
+    #[test]
+    fn beneficiary_and_delegate() {
+        let authorities = MemberAuthorities {
+            beneficiary: Pubkey::new_rand(),
+            delegate: Pubkey::new_rand(),
+        };
+        let beneficiary = authorities.beneficiary;
+        let delegate = authorities.delegate;
+        let other = Pubkey::new_rand();
+
+        assert!(authorities.is_beneficiary(&beneficiary));
+        assert!(!authorities.is_delegate(&beneficiary));
+        assert!(authorities.is_delegate(&delegate));
+        assert!(!authorities.is_beneficiary(&delegate));
+        assert!(!authorities.is_beneficiary(&other));
+        assert!(!authorities.is_delegate(&other));
+    }
+
+    #[test]
+    fn unset_delegate() {
+        let authorities = MemberAuthorities {
+            beneficiary: Pubkey::new_rand(),
+            delegate: Pubkey::default(),
+        };
+
+        assert_eq!(authorities.delegate(), None);
+        assert!(!authorities.is_delegate(&Pubkey::default()));
+        assert!(!authorities.is_beneficiary(&Pubkey::default()));
+        assert!(authorities.is_beneficiary(&authorities.beneficiary));
+    }
+
+    #[test]
+    fn stake_bounds() {
+        let mut member = Member::default();
+        member.add_stake(u64::MAX, 1).unwrap();
+        assert!(member.add_stake(1, 0).is_err());
+        assert!(member.add_stake(0, u64::MAX).is_err());
+        assert_eq!((member.amount, member.mega_amount), (u64::MAX, 1));
+
+        member.sub_stake(u64::MAX, 0).unwrap();
+        assert!(member.sub_stake(1, 0).is_err());
+        assert!(member.sub_stake(0, 2).is_err());
+        assert_eq!((member.amount, member.mega_amount), (0, 1));
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use serum_common::pack::*;
+use solana_client_gen::solana_sdk::pubkey::Pubkey;
+
+/// Maximum length, in bytes, of `EntityMetadata::name`.
+pub const MAX_NAME_LEN: usize = 64;
+/// Maximum length, in bytes, of `EntityMetadata::url`.
+pub const MAX_URL_LEN: usize = 128;
+/// Maximum length, in bytes, of `EntityMetadata::description`.
+pub const MAX_DESCRIPTION_LEN: usize = 512;
+
+/// Size of the account holding an `EntityMetadata`, i.e., the size of the
+/// serialization when all fields are at their maximum length. Each string is
+/// prefixed with its u64 length.
+pub const SIZE: u64 =
+    (DISCRIMINATOR_LEN + 1 + 32 + 8 + MAX_NAME_LEN + 8 + MAX_URL_LEN + 8 + MAX_DESCRIPTION_LEN)
+        as u64;
+
+/// Prefix identifying EntityMetadata accounts.
+pub const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"srm:meta";
+
+/// EntityMetadata is an optional account a leader can create to advertise
+/// its entity.
+///
+/// Unlike the other accounts, the serialization is variable length. The
+/// account is allocated with `SIZE` bytes and any unused bytes at the end
+/// are left zeroed.
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EntityMetadata {
+    /// Set by the program on creation.
+    pub initialized: bool,
+    /// The entity this metadata describes.
+    pub entity: Pubkey,
+    /// Display name of the entity.
+    pub name: String,
+    /// Website of the entity.
+    pub url: String,
+    /// Free form description, e.g., contact info.
+    pub description: String,
+}
+
+impl EntityMetadata {
+    /// Returns true if all fields are within their maximum lengths.
+    pub fn is_valid(&self) -> bool {
+        self.name.len() <= MAX_NAME_LEN
+            && self.url.len() <= MAX_URL_LEN
+            && self.description.len() <= MAX_DESCRIPTION_LEN
+    }
+}
+
+impl Pack for EntityMetadata {
+    fn pack(src: EntityMetadata, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if src.size()? > dst.len() as u64 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // Zero the tail so that shrinking a field doesn't leave stale bytes.
+        for b in dst.iter_mut() {
+            *b = 0;
+        }
+        serum_common::pack::into_bytes_with_discriminator(&DISCRIMINATOR, &src, dst)
+    }
+
+    fn unpack_unchecked(src: &mut &[u8]) -> Result<EntityMetadata, ProgramError> {
+        serum_common::pack::from_reader_with_discriminator(&DISCRIMINATOR, src)
+    }
+
+    fn size(&self) -> Result<u64, ProgramError> {
+        serum_common::pack::bytes_size(&self).map(|size| size + DISCRIMINATOR_LEN as u64)
+    }
+
+    // The account is larger than the serialization, so trailing bytes are
+    // expected.
+    fn unpack(src: &[u8]) -> Result<EntityMetadata, ProgramError> {
+        let mut src_mut = src;
+        EntityMetadata::unpack_unchecked(&mut src_mut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_size() {
+        let metadata = EntityMetadata {
+            initialized: true,
+            entity: Pubkey::new_rand(),
+            name: "n".repeat(MAX_NAME_LEN),
+            url: "u".repeat(MAX_URL_LEN),
+            description: "d".repeat(MAX_DESCRIPTION_LEN),
+        };
+        assert!(metadata.is_valid());
+        assert_eq!(metadata.size().unwrap(), SIZE);
+    }
+
+    #[test]
+    fn shrink_and_grow() {
+        let entity = Pubkey::new_rand();
+        let mut data = vec![0u8; SIZE as usize];
+
+        let metadata = EntityMetadata {
+            initialized: true,
+            entity,
+            name: "a long entity name".to_string(),
+            url: "https://example.com".to_string(),
+            description: "contact: ops@example.com".to_string(),
+        };
+        EntityMetadata::pack(metadata, &mut data).unwrap();
+
+        EntityMetadata::unpack_mut(&mut data, &mut |m: &mut EntityMetadata| {
+            m.name = "short".to_string();
+            m.description = String::new();
+            Ok(())
+        })
+        .unwrap();
+
+        let metadata = EntityMetadata::unpack(&data).unwrap();
+        assert_eq!(
+            metadata,
+            EntityMetadata {
+                initialized: true,
+                entity,
+                name: "short".to_string(),
+                url: "https://example.com".to_string(),
+                description: String::new(),
+            }
+        );
+        let used = metadata.size().unwrap() as usize;
+        assert!(data[used..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn too_long() {
+        let metadata = EntityMetadata {
+            name: "n".repeat(MAX_NAME_LEN + 1),
+            ..Default::default()
+        };
+        assert!(!metadata.is_valid());
+    }
+
+    #[test]
+    fn zeroed_is_uninitialized() {
+        let data = vec![0u8; SIZE as usize];
+        let metadata = EntityMetadata::unpack(&data).unwrap();
+        assert!(!metadata.initialized);
+    }
+}
@@ -1,7 +1,34 @@
+pub mod capability_registry;
 pub mod entity;
 pub mod member;
+pub mod metadata;
+pub mod multisig;
 pub mod registrar;
 
+pub use capability_registry::{CapabilityDescriptor, CapabilityRegistry};
 pub use entity::{Entity, StakeKind};
-pub use member::Member;
+pub use member::{Member, MemberAuthorities};
+pub use metadata::EntityMetadata;
+pub use multisig::Multisig;
 pub use registrar::Registrar;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discriminators_unique() {
+        let discriminators = [
+            capability_registry::DISCRIMINATOR,
+            entity::DISCRIMINATOR,
+            member::DISCRIMINATOR,
+            metadata::DISCRIMINATOR,
+            multisig::DISCRIMINATOR,
+            registrar::DISCRIMINATOR,
+        ];
+        for (i, d) in discriminators.iter().enumerate() {
+            assert_ne!(*d, [0; 8]);
+            assert!(!discriminators[..i].contains(d));
+        }
+    }
+}
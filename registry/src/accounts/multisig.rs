@@ -0,0 +1,98 @@
+use serum_common::pack::*;
+use solana_client_gen::solana_sdk::pubkey::Pubkey;
+
+#[cfg(feature = "client")]
+lazy_static::lazy_static! {
+    pub static ref SIZE: u64 = Multisig::default()
+                .size()
+                .expect("Multisig has a fixed size");
+}
+
+/// Maximum number of signers in a Multisig.
+pub const MAX_SIGNERS: usize = 11;
+
+/// Multisig is an m-of-n set of keys that can act as an entity leader in
+/// place of a single keypair.
+#[derive(Default, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Multisig {
+    /// Set by the program on creation.
+    pub initialized: bool,
+    /// Number of signers required.
+    pub m: u8,
+    /// Number of valid signers, i.e., the prefix of `signers` in use.
+    pub n: u8,
+    /// Signer keys.
+    pub signers: [Pubkey; MAX_SIGNERS],
+}
+
+impl Multisig {
+    /// Returns the signer keys in use.
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..usize::from(self.n).min(MAX_SIGNERS)]
+    }
+
+    /// Returns true if at least `m` distinct keys of the multisig are in
+    /// `signed`.
+    pub fn is_approved(&self, signed: &[&Pubkey]) -> bool {
+        let approvals = self.signers().iter().filter(|s| signed.contains(s)).count();
+        approvals >= usize::from(self.m)
+    }
+}
+
+/// Returns true if the given keys and threshold make a valid Multisig, i.e.,
+/// `1 <= m <= n <= MAX_SIGNERS` and no key is repeated.
+pub fn is_valid(m: u8, signers: &[Pubkey]) -> bool {
+    if m == 0 || usize::from(m) > signers.len() || signers.len() > MAX_SIGNERS {
+        return false;
+    }
+    signers
+        .iter()
+        .enumerate()
+        .all(|(i, s)| !signers[..i].contains(s))
+}
+
+/// Prefix identifying Multisig accounts.
+pub const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"srm:msig";
+
+serum_common::packable!(Multisig, DISCRIMINATOR);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validity() {
+        let a = Pubkey::new_rand();
+        let b = Pubkey::new_rand();
+        assert!(is_valid(1, &[a]));
+        assert!(is_valid(2, &[a, b]));
+        assert!(!is_valid(0, &[a, b]));
+        assert!(!is_valid(3, &[a, b]));
+        assert!(!is_valid(1, &[a, a]));
+        assert!(!is_valid(1, &[a; MAX_SIGNERS + 1]));
+    }
+
+    #[test]
+    fn approval() {
+        let a = Pubkey::new_rand();
+        let b = Pubkey::new_rand();
+        let c = Pubkey::new_rand();
+        let mut signers = [Pubkey::default(); MAX_SIGNERS];
+        signers[..3].copy_from_slice(&[a, b, c]);
+        let multisig = Multisig {
+            initialized: true,
+            m: 2,
+            n: 3,
+            signers,
+        };
+
+        assert!(!multisig.is_approved(&[]));
+        assert!(!multisig.is_approved(&[&a]));
+        assert!(!multisig.is_approved(&[&a, &a]));
+        assert!(!multisig.is_approved(&[&a, &Pubkey::new_rand()]));
+        // Unused slots don't count.
+        assert!(!multisig.is_approved(&[&a, &Pubkey::default()]));
+        assert!(multisig.is_approved(&[&a, &c]));
+        assert!(multisig.is_approved(&[&a, &b, &c]));
+    }
+}
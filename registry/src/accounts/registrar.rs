@@ -5,9 +5,21 @@ use solana_client_gen::solana_sdk::pubkey::Pubkey;
 lazy_static::lazy_static! {
     pub static ref SIZE: u64 = Registrar::default()
                 .size()
-                .expect("Vesting has a fixed size");
+                .expect("Vesting has a fixed size")
+                + RESERVED_LEN as u64;
 }
 
+/// Number of capability slots available in a Registrar's fee table. Valid
+/// ids are `0..MAX_CAPABILITIES`. Registrars needing more capabilities
+/// create a CapabilityRegistry.
+pub const MAX_CAPABILITIES: usize = 32;
+
+/// Upper bound on a capability fee, i.e., 100%.
+pub const MAX_CAPABILITY_FEE_BPS: u32 = 10_000;
+
+/// Number of beneficiaries a Registrar can block from joining entities.
+pub const MAX_BLOCKLIST: usize = 16;
+
 /// Registry defines the account representing an instance of the program.
 #[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Registrar {
@@ -15,13 +27,48 @@ pub struct Registrar {
     pub initialized: bool,
     /// Priviledged account with the ability to register capabilities.
     pub authority: Pubkey,
+    /// Account proposed to become the authority, which must sign off to
+    /// take over. Zero if no transfer is in progress.
+    pub pending_authority: Pubkey,
     /// Maps capability identifier to the bps fee rate earned for the capability.
-    pub capabilities_fees_bps: [u32; 32],
+    pub capabilities_fees_bps: [u32; MAX_CAPABILITIES],
     /// Number of slots that must pass for a withdrawal to complete.
     pub withdrawal_timelock: u64,
+    /// If set, the registrar authority can't also be the leader of an entity.
+    pub separate_leader_and_authority: bool,
+    /// Beneficiaries that can't join any entity. Empty slots are zeroed.
+    pub blocklist: [Pubkey; MAX_BLOCKLIST],
+    /// Set by the authority to halt entity creation and membership changes,
+    /// e.g., while responding to an incident.
+    pub paused: bool,
+    /// CapabilityRegistry describing this registrar's capabilities, which
+    /// supersedes `capabilities_fees_bps` once set. Zero if not created.
+    pub capability_registry: Pubkey,
+    /// Number of slots after an entity's last heartbeat before it's stale
+    /// and can no longer take on members. Zero disables the check.
+    pub heartbeat_timeout: u64,
+}
+
+/// Returns true if the capability id has a slot in the registrar.
+pub fn is_valid_capability_id(capability_id: u8) -> bool {
+    usize::from(capability_id) < MAX_CAPABILITIES
 }
 
 impl Registrar {
+    /// Returns the capability's fee, or None if the id is invalid.
+    pub fn capability_fee_bps(&self, capability_id: u8) -> Option<u32> {
+        self.capabilities_fees_bps
+            .get(usize::from(capability_id))
+            .copied()
+    }
+
+    /// Returns the capability's fee for updating, or None if the id is
+    /// invalid.
+    pub fn capability_fee_bps_mut(&mut self, capability_id: u8) -> Option<&mut u32> {
+        self.capabilities_fees_bps
+            .get_mut(usize::from(capability_id))
+    }
+
     /// Returns the capability id of the next available slot. Otherwise None,
     /// if full.
     pub fn next_free_capability_id(&self) -> Option<u8> {
@@ -32,6 +79,90 @@ impl Registrar {
         }
         None
     }
+
+    pub fn is_blocked(&self, beneficiary: &Pubkey) -> bool {
+        *beneficiary != Pubkey::default() && self.blocklist.contains(beneficiary)
+    }
+
+    /// Adds the beneficiary to the blocklist. Returns false if there's no
+    /// space left.
+    pub fn block(&mut self, beneficiary: Pubkey) -> bool {
+        if self.is_blocked(&beneficiary) {
+            return true;
+        }
+        match self.blocklist.iter_mut().find(|b| **b == Pubkey::default()) {
+            None => false,
+            Some(slot) => {
+                *slot = beneficiary;
+                true
+            }
+        }
+    }
+
+    pub fn unblock(&mut self, beneficiary: &Pubkey) {
+        for b in self.blocklist.iter_mut() {
+            if b == beneficiary {
+                *b = Pubkey::default();
+            }
+        }
+    }
+}
+
+/// Prefix identifying Registrar accounts.
+pub const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"srm:rgst";
+
+serum_common::versioned_packable!(Registrar, DISCRIMINATOR);
+
+impl Versioned for Registrar {
+    const VERSION: u8 = 1;
+
+    fn upgrade(_version: u8, _src: &mut &[u8]) -> Result<Self, ProgramError> {
+        // No older layouts.
+        Err(ProgramError::InvalidAccountData)
+    }
 }
 
-serum_common::packable!(Registrar);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capability_id_bounds() {
+        let mut registrar = Registrar::default();
+        for id in &[0, 31] {
+            assert!(is_valid_capability_id(*id));
+            *registrar.capability_fee_bps_mut(*id).unwrap() = 7;
+            assert_eq!(registrar.capability_fee_bps(*id), Some(7));
+        }
+        for id in &[32, 255] {
+            assert!(!is_valid_capability_id(*id));
+            assert!(registrar.capability_fee_bps_mut(*id).is_none());
+            assert_eq!(registrar.capability_fee_bps(*id), None);
+        }
+    }
+
+    #[test]
+    fn blocklist() {
+        let mut registrar = Registrar::default();
+        assert!(!registrar.is_blocked(&Pubkey::default()));
+
+        let blocked: Vec<Pubkey> = (0..MAX_BLOCKLIST).map(|_| Pubkey::new_rand()).collect();
+        for b in &blocked {
+            assert!(registrar.block(*b));
+        }
+        assert!(blocked.iter().all(|b| registrar.is_blocked(b)));
+
+        // Full, but blocking an existing entry is a no-op.
+        assert!(!registrar.block(Pubkey::new_rand()));
+        assert!(registrar.block(blocked[0]));
+
+        registrar.unblock(&blocked[3]);
+        assert!(!registrar.is_blocked(&blocked[3]));
+        assert!(!registrar.is_blocked(&Pubkey::default()));
+
+        // Freed slots are reused.
+        let other = Pubkey::new_rand();
+        assert!(registrar.block(other));
+        assert!(registrar.is_blocked(&other));
+    }
+}
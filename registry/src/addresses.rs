@@ -0,0 +1,81 @@
+//! Derived addresses of registry accounts.
+//!
+//! Entity and Member accounts are created by clients with
+//! `create_account_with_seed`, based on the key signing for their creation,
+//! so their addresses can be found again from that key alone. The program
+//! doesn't check these addresses; only the client and CLI derive them.
+//!
+//! EntityMetadata accounts live at a program derived address of their
+//! entity, which the program creates and checks, and clients look up, with
+//! `metadata`.
+
+use solana_client_gen::solana_sdk::pubkey::{Pubkey, PubkeyError};
+
+pub const ENTITY_SEED: &str = "srm:registry:entity";
+pub const MEMBER_SEED: &str = "srm:registry:member";
+pub const METADATA_SEED: &str = "srm:registry:metadata";
+
+/// Address of the Entity created by `leader` with the client.
+pub fn entity(leader: &Pubkey, program_id: &Pubkey) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_with_seed(leader, ENTITY_SEED, program_id)
+}
+
+/// Address of the Member created, i.e., paid for, by `owner` with the
+/// client.
+pub fn member(owner: &Pubkey, program_id: &Pubkey) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_with_seed(owner, MEMBER_SEED, program_id)
+}
+
+/// Address of the given entity's EntityMetadata, along with the nonce
+/// making it a program derived address. The program creates the account
+/// itself, at the first nonce (counting down from 255) giving a valid
+/// address, so it can be found from the entity alone, whoever leads it.
+pub fn metadata(entity: &Pubkey, program_id: &Pubkey) -> Result<(Pubkey, u8), PubkeyError> {
+    for nonce in (0..=u8::MAX).rev() {
+        if let Ok(address) =
+            Pubkey::create_program_address(&metadata_signer_seeds(entity, &nonce), program_id)
+        {
+            return Ok((address, nonce));
+        }
+    }
+    Err(PubkeyError::InvalidSeeds)
+}
+
+/// Seeds the program signs with when creating the EntityMetadata account.
+pub fn metadata_signer_seeds<'a>(entity: &'a Pubkey, nonce: &'a u8) -> [&'a [u8]; 3] {
+    [
+        METADATA_SEED.as_bytes(),
+        entity.as_ref(),
+        bytemuck::bytes_of(nonce),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distinct_per_account_type() {
+        let base = Pubkey::new_rand();
+        let program_id = Pubkey::new_rand();
+        let entity = entity(&base, &program_id).unwrap();
+        let member = member(&base, &program_id).unwrap();
+        let (metadata, nonce) = metadata(&base, &program_id).unwrap();
+        assert_ne!(entity, member);
+        assert_ne!(entity, metadata);
+        assert_ne!(member, metadata);
+
+        assert_eq!(
+            entity,
+            Pubkey::create_with_seed(&base, "srm:registry:entity", &program_id).unwrap()
+        );
+        assert_eq!(
+            metadata,
+            Pubkey::create_program_address(
+                &[b"srm:registry:metadata", base.as_ref(), &[nonce]],
+                &program_id
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! A read-through cache over the registry client for callers, e.g.
+//! dashboards, that repeatedly read the same registrar and entity accounts.
+
+use crate::accounts::{Entity, Registrar};
+use crate::client::{Client, ClientError};
+use serum_common::pack::Pack;
+use solana_client_gen::solana_sdk::pubkey::Pubkey;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub struct CachingClient {
+    client: Client,
+    ttl: Duration,
+    // Raw account data keyed by address, along with the time it was fetched.
+    accounts: RefCell<HashMap<Pubkey, (Instant, Vec<u8>)>>,
+}
+
+impl CachingClient {
+    /// Wraps the client, caching account reads for `ttl`.
+    pub fn new(client: Client, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            accounts: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The underlying client. Reads through it bypass the cache.
+    pub fn inner(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn registrar(&self, address: &Pubkey) -> Result<Registrar, ClientError> {
+        self.account(address)
+    }
+
+    pub fn entity(&self, address: &Pubkey) -> Result<Entity, ClientError> {
+        self.account(address)
+    }
+
+    /// Runs a mutation with the underlying client, dropping the cached copies
+    /// of the given accounts whether or not it succeeds, since a failed send
+    /// may still have landed.
+    pub fn mutate<T>(
+        &self,
+        written: &[Pubkey],
+        f: impl FnOnce(&Client) -> Result<T, ClientError>,
+    ) -> Result<T, ClientError> {
+        let result = f(&self.client);
+        for address in written {
+            self.invalidate(address);
+        }
+        result
+    }
+
+    pub fn invalidate(&self, address: &Pubkey) {
+        self.accounts.borrow_mut().remove(address);
+    }
+
+    pub fn invalidate_all(&self) {
+        self.accounts.borrow_mut().clear();
+    }
+
+    fn account<T: Pack>(&self, address: &Pubkey) -> Result<T, ClientError> {
+        if let Some((fetched_at, data)) = self.accounts.borrow().get(address) {
+            if fetched_at.elapsed() < self.ttl {
+                return T::unpack(data).map_err(|e| ClientError::RawError(e.to_string()));
+            }
+        }
+        let data = self.client.rpc().get_account_data(address)?;
+        let account = T::unpack(&data).map_err(|e| ClientError::RawError(e.to_string()))?;
+        self.accounts
+            .borrow_mut()
+            .insert(*address, (Instant::now(), data));
+        Ok(account)
+    }
+}
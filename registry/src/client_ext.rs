@@ -1,4 +1,6 @@
+use crate::accounts::capability_registry;
 use crate::accounts::member;
+use crate::accounts::multisig;
 use crate::accounts::registrar;
 use serum_common::pack::Pack;
 use solana_client_gen::prelude::*;
@@ -7,13 +9,44 @@ use solana_client_gen::solana_sdk::instruction::AccountMeta;
 use solana_client_gen::solana_sdk::pubkey::Pubkey;
 use solana_client_gen::solana_sdk::signers::Signers;
 use solana_client_gen::solana_sdk::system_instruction;
+use std::collections::HashSet;
+
+/// A registrar along with every entity created with it and every member of
+/// those entities.
+#[derive(Debug)]
+pub struct RegistrySnapshot {
+    pub registrar: crate::accounts::Registrar,
+    pub entities: Vec<(Pubkey, crate::accounts::Entity)>,
+    pub members: Vec<(Pubkey, crate::accounts::Member)>,
+}
+
+/// A member along with the entity and registrar it belongs to, all read at
+/// the same slot.
+#[derive(Debug)]
+pub struct MemberSnapshot {
+    pub registrar: crate::accounts::Registrar,
+    pub entity: crate::accounts::Entity,
+    pub member: crate::accounts::Member,
+}
+
+/// Outcome of a transaction run through simulateTransaction.
+#[derive(Debug)]
+pub struct Simulation {
+    /// The error the transaction would have failed with, if any.
+    pub err: Option<solana_sdk::transaction::TransactionError>,
+    /// Program logs, including those of failed instructions.
+    pub logs: Vec<String>,
+    /// Registry events decoded from the logs.
+    pub events: Vec<crate::events::RegistryEvent>,
+}
 
 solana_client_gen_extension! {
     impl Client {
         pub fn create_entity_derived(
             &self,
             leader_kp: &Keypair,
-            capabilities: u32,
+            registrar: Pubkey,
+            capabilities: u64,
             stake_kind: crate::accounts::StakeKind,
         ) -> Result<(Signature, Pubkey), ClientError> {
             let entity_account_size = *crate::accounts::entity::SIZE;
@@ -33,11 +66,13 @@ solana_client_gen_extension! {
                     &self.program(),          // Owner.
                 );
 
-            let accounts = [
+            let mut accounts = vec![
                 AccountMeta::new(entity_address, false),
                 AccountMeta::new_readonly(leader_kp.pubkey(), true),
+                AccountMeta::new_readonly(registrar, false),
                 AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
             ];
+            accounts.extend(self.capability_registry_account(&registrar)?);
             let create_entity_instr = super::instruction::create_entity(
                 *self.program(),
                 &accounts,
@@ -73,6 +108,7 @@ solana_client_gen_extension! {
         pub fn join_entity_derived(
             &self,
             entity: Pubkey,
+            registrar: Pubkey,
             beneficiary: Pubkey,
             delegate: Pubkey,
         ) -> Result<(Signature, Pubkey), ClientError> {
@@ -97,7 +133,9 @@ solana_client_gen_extension! {
             let accounts = [
                 AccountMeta::new(member_address, false),
                 AccountMeta::new(entity, false),
+                AccountMeta::new_readonly(registrar, false),
                 AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+                AccountMeta::new_readonly(solana_sdk::sysvar::clock::ID, false),
             ];
 
             let member_instr = super::instruction::join_entity(
@@ -133,28 +171,452 @@ solana_client_gen_extension! {
                 .map(|sig| (sig, member_address))
         }
 
+        /// Sets the fee for every (capability_id, fee_bps) pair in the given
+        /// schedule with a single transaction. The schedule is validated
+        /// before anything is sent.
+        ///
+        /// Once the registrar has a CapabilityRegistry, the fees are set
+        /// there, with SetCapability, and ids can go up to
+        /// `capability_registry::MAX_CAPABILITIES`. Those instructions carry
+        /// a label hash, so a long schedule can exceed the transaction size
+        /// limit, in which case sending fails and nothing is applied. New
+        /// capabilities get a zero label hash and existing ones keep theirs.
+        pub fn set_fee_schedule(
+            &self,
+            registrar: &Pubkey,
+            registrar_authority: &Keypair,
+            schedule: &[(u8, u32)],
+        ) -> Result<Signature, ClientError> {
+            let registrar_account: crate::accounts::Registrar =
+                serum_common::client::rpc::get_account(self.rpc(), registrar)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+            let capability_registry = registrar_account.capability_registry;
+            let is_migrated = capability_registry != Pubkey::default();
+
+            let mut seen = [false; crate::accounts::capability_registry::MAX_CAPABILITIES];
+            for (capability_id, fee_bps) in schedule {
+                let is_valid = if is_migrated {
+                    crate::accounts::capability_registry::is_valid_capability_id(*capability_id)
+                } else {
+                    crate::accounts::registrar::is_valid_capability_id(*capability_id)
+                };
+                if !is_valid {
+                    return Err(ClientError::RawError(format!(
+                        "invalid capability id: {}",
+                        capability_id
+                    )));
+                }
+                if *fee_bps > crate::accounts::registrar::MAX_CAPABILITY_FEE_BPS {
+                    return Err(ClientError::RawError(format!(
+                        "invalid fee for capability {}: {} bps",
+                        capability_id, fee_bps
+                    )));
+                }
+                let idx = usize::from(*capability_id);
+                if seen[idx] {
+                    return Err(ClientError::RawError(format!(
+                        "duplicate capability id: {}",
+                        capability_id
+                    )));
+                }
+                seen[idx] = true;
+            }
+
+            let instructions: Vec<Instruction> = if is_migrated {
+                // Only the fee is being set, so keep any existing labels.
+                let registry: crate::accounts::CapabilityRegistry =
+                    serum_common::client::rpc::get_account(self.rpc(), &capability_registry)
+                        .map_err(|e| ClientError::RawError(e.to_string()))?;
+                let accounts = [
+                    AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+                    AccountMeta::new_readonly(*registrar, false),
+                    AccountMeta::new(capability_registry, false),
+                ];
+                schedule
+                    .iter()
+                    .map(|(capability_id, fee_bps)| {
+                        super::instruction::set_capability(
+                            *self.program(),
+                            &accounts,
+                            *capability_id,
+                            *fee_bps,
+                            registry
+                                .get(*capability_id)
+                                .map(|c| c.label_hash)
+                                .unwrap_or([0; 32]),
+                        )
+                    })
+                    .collect()
+            } else {
+                // With duplicates rejected there are at most
+                // MAX_CAPABILITIES instructions, which comfortably fit in a
+                // single transaction.
+                let accounts = [
+                    AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+                    AccountMeta::new(*registrar, false),
+                ];
+                schedule
+                    .iter()
+                    .map(|(capability_id, fee_bps)| {
+                        super::instruction::register_capability(
+                            *self.program(),
+                            &accounts,
+                            *capability_id,
+                            *fee_bps,
+                        )
+                    })
+                    .collect()
+            };
+            let signers = [registrar_authority, self.payer()];
+            let (recent_hash, _fee_calc) = self
+                .rpc()
+                .get_recent_blockhash()?;
+
+            let tx = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&self.payer().pubkey()),
+                &signers,
+                recent_hash,
+            );
+
+            self
+                .rpc()
+                .send_and_confirm_transaction_with_spinner_and_config(
+                    &tx,
+                    self.options().commitment,
+                    self.options().tx,
+                )
+                .map_err(ClientError::RpcError)
+        }
+
+        /// Returns the lamports the payer would be charged for a transaction
+        /// made of the given instructions, at the cluster's current fee rate.
+        pub fn estimate_fee(&self, instructions: &[Instruction]) -> Result<u64, ClientError> {
+            let (_recent_hash, fee_calc) = self
+                .rpc()
+                .get_recent_blockhash()?;
+            let tx = Transaction::new_with_payer(
+                instructions,
+                Some(&self.payer().pubkey()),
+            );
+            Ok(fee_calc.calculate_fee(&tx.message))
+        }
+
         pub fn entity_address_derived(&self, leader: &Pubkey) -> Result<Pubkey, ClientError> {
-            Pubkey::create_with_seed(
-                leader,
-                Self::entity_seed(),
-                &self.program(),
-            ).map_err(|e| ClientError::RawError(e.to_string()))
+            crate::addresses::entity(leader, &self.program())
+                .map_err(|e| ClientError::RawError(e.to_string()))
         }
 
         pub fn entity_seed() -> &'static str {
-            "srm:registry:entity"
+            crate::addresses::ENTITY_SEED
         }
 
         pub fn member_address_derived(&self) -> Result<Pubkey, ClientError> {
-            Pubkey::create_with_seed(
-                &self.payer().pubkey(),
-                Self::member_seed(),
-                &self.program(),
-            ).map_err(|e| ClientError::RawError(e.to_string()))
+            crate::addresses::member(&self.payer().pubkey(), &self.program())
+                .map_err(|e| ClientError::RawError(e.to_string()))
         }
 
         pub fn member_seed() -> &'static str {
-            "srm:registry:member"
+            crate::addresses::MEMBER_SEED
+        }
+
+        /// Creates and initializes the EntityMetadata account for the
+        /// entity led by `leader_kp`.
+        pub fn create_metadata_derived(
+            &self,
+            leader_kp: &Keypair,
+            entity: Pubkey,
+            name: String,
+            url: String,
+            description: String,
+        ) -> Result<(Signature, Pubkey), ClientError> {
+            let metadata_address = self.metadata_address_derived(&entity)?;
+            let accounts = [
+                AccountMeta::new(metadata_address, false),
+                AccountMeta::new_readonly(leader_kp.pubkey(), true),
+                AccountMeta::new_readonly(entity, false),
+                AccountMeta::new(self.payer().pubkey(), true),
+                AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            ];
+            self.create_metadata_with_signers(
+                &[leader_kp, self.payer()],
+                &accounts,
+                name,
+                url,
+                description,
+            )
+            .map(|sig| (sig, metadata_address))
+        }
+
+        /// Address of the given entity's EntityMetadata account.
+        pub fn metadata_address_derived(&self, entity: &Pubkey) -> Result<Pubkey, ClientError> {
+            crate::addresses::metadata(entity, &self.program())
+                .map(|(address, _nonce)| address)
+                .map_err(|e| ClientError::RawError(e.to_string()))
+        }
+
+        /// Returns the registrar's CapabilityRegistry as the account
+        /// instructions setting capabilities expect after their fixed
+        /// accounts, or None if the registrar has none.
+        pub fn capability_registry_account(
+            &self,
+            registrar: &Pubkey,
+        ) -> Result<Option<AccountMeta>, ClientError> {
+            let registrar: crate::accounts::Registrar =
+                serum_common::client::rpc::get_account(self.rpc(), registrar)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+            if registrar.capability_registry == Pubkey::default() {
+                return Ok(None);
+            }
+            Ok(Some(AccountMeta::new_readonly(registrar.capability_registry, false)))
+        }
+
+        /// Returns the address and contents of the given entity's metadata
+        /// account, if it has one.
+        pub fn entity_metadata(
+            &self,
+            entity: &Pubkey,
+        ) -> Result<Option<(Pubkey, crate::accounts::EntityMetadata)>, ClientError> {
+            let address = self.metadata_address_derived(entity)?;
+            let account = match self
+                .rpc()
+                .get_account_with_commitment(&address, self.options().commitment)?
+                .value
+            {
+                None => return Ok(None),
+                Some(account) => account,
+            };
+            let m = crate::accounts::EntityMetadata::unpack(&account.data)
+                .map_err(|e| ClientError::RawError(e.to_string()))?;
+            if !m.initialized || m.entity != *entity {
+                return Ok(None);
+            }
+            Ok(Some((address, m)))
+        }
+
+        /// Hands the member over to `new_beneficiary`, reading the account
+        /// back to confirm the change landed.
+        pub fn set_beneficiary_confirmed(
+            &self,
+            member: &Pubkey,
+            beneficiary_kp: &Keypair,
+            new_beneficiary: Pubkey,
+        ) -> Result<(Signature, crate::accounts::Member), ClientError> {
+            let member_account: crate::accounts::Member =
+                serum_common::client::rpc::get_account(self.rpc(), member)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+            let entity: crate::accounts::Entity =
+                serum_common::client::rpc::get_account(self.rpc(), &member_account.entity)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+
+            let accounts = [
+                AccountMeta::new(*member, false),
+                AccountMeta::new_readonly(beneficiary_kp.pubkey(), true),
+                AccountMeta::new_readonly(member_account.entity, false),
+                AccountMeta::new_readonly(entity.registrar, false),
+            ];
+            let sig = self.set_beneficiary_with_signers(
+                &[beneficiary_kp, self.payer()],
+                &accounts,
+                new_beneficiary,
+            )?;
+
+            let member_account: crate::accounts::Member =
+                serum_common::client::rpc::get_account(self.rpc(), member)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+            if !member_account.authorities.is_beneficiary(&new_beneficiary) {
+                return Err(ClientError::RawError(format!(
+                    "beneficiary of {} not updated",
+                    member
+                )));
+            }
+            Ok((sig, member_account))
+        }
+
+        /// Fetches all the given Member accounts in as few requests as
+        /// possible, returning `None` for any address with no account.
+        pub fn members(&self, addresses: &[Pubkey]) -> Result<Vec<Option<crate::accounts::Member>>, ClientError> {
+            serum_common::client::rpc::get_accounts(self.rpc(), addresses)
+                .map_err(|e| ClientError::RawError(e.to_string()))
+        }
+
+        /// Reads the full registry graph under the given registrar with a
+        /// single getProgramAccounts request, telling the account types
+        /// apart the same way `entities` and `entity_members` do.
+        pub fn full_snapshot(&self, registrar: &Pubkey) -> Result<RegistrySnapshot, ClientError> {
+            let accounts = self.rpc().get_program_accounts(self.program())?;
+
+            let registrar_account = accounts
+                .iter()
+                .find(|(address, _)| address == registrar)
+                .map(|(_, account)| crate::accounts::Registrar::unpack(&account.data))
+                .transpose()
+                .map_err(|e| ClientError::RawError(e.to_string()))?
+                .filter(|r| r.initialized)
+                .ok_or_else(|| {
+                    ClientError::RawError(format!("registrar not found: {}", registrar))
+                })?;
+            let entities = Self::registrar_entities(&accounts, registrar)?;
+            let entity_addresses: HashSet<Pubkey> = entities.iter().map(|(a, _)| *a).collect();
+            let members =
+                Self::filter_members(&accounts, |entity| entity_addresses.contains(entity))?;
+
+            Ok(RegistrySnapshot {
+                registrar: registrar_account,
+                entities,
+                members,
+            })
+        }
+
+        /// Signs the instructions as a single transaction, paid for by the
+        /// client's payer, and simulates it instead of sending it, so
+        /// that nothing is committed to the cluster.
+        pub fn simulate<T: Signers>(
+            &self,
+            signers: &T,
+            instructions: &[Instruction],
+        ) -> Result<Simulation, ClientError> {
+            let (recent_hash, _fee_calc) = self.rpc().get_recent_blockhash()?;
+            let tx = Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.payer().pubkey()),
+                signers,
+                recent_hash,
+            );
+            let result = serum_common::client::rpc::simulate_transaction(
+                self.rpc(),
+                &tx,
+                true,
+                CommitmentConfig::recent(),
+            )?
+            .value;
+
+            let logs = result.logs.unwrap_or_default();
+            let events = crate::events::decode_logs(self.program(), &logs);
+            Ok(Simulation {
+                err: result.err,
+                logs,
+                events,
+            })
+        }
+
+        /// Returns every entity created with the given registrar.
+        pub fn entities(
+            &self,
+            registrar: &Pubkey,
+        ) -> Result<Vec<(Pubkey, crate::accounts::Entity)>, ClientError> {
+            // The pinned solana-client (1.3.14) only has the unfiltered
+            // `get_program_accounts`, without the config taking memcmp or
+            // data size filters, so this fetches every program account and
+            // filters here. Switch to a memcmp on the registrar when
+            // upgrading.
+            let accounts = self.rpc().get_program_accounts(self.program())?;
+            Self::registrar_entities(&accounts, registrar)
+        }
+
+        /// Returns every member of the given entity.
+        pub fn entity_members(
+            &self,
+            entity: &Pubkey,
+        ) -> Result<Vec<(Pubkey, crate::accounts::Member)>, ClientError> {
+            // Unfiltered for the same reason as `entities`.
+            let accounts = self.rpc().get_program_accounts(self.program())?;
+            Self::filter_members(&accounts, |e| e == entity)
+        }
+
+        // Initialized entities of the given registrar among the program's
+        // accounts.
+        fn registrar_entities(
+            accounts: &[(Pubkey, solana_sdk::account::Account)],
+            registrar: &Pubkey,
+        ) -> Result<Vec<(Pubkey, crate::accounts::Entity)>, ClientError> {
+            let mut entities = vec![];
+            for (address, account) in accounts {
+                if account.data.len() as u64 != *crate::accounts::entity::SIZE
+                    || !account.data.starts_with(&crate::accounts::entity::DISCRIMINATOR)
+                {
+                    continue;
+                }
+                let e = crate::accounts::Entity::unpack(&account.data)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+                if e.initialized && e.registrar == *registrar {
+                    entities.push((*address, e));
+                }
+            }
+            Ok(entities)
+        }
+
+        // Initialized members among the program's accounts whose entity
+        // satisfies the given predicate.
+        fn filter_members<F: Fn(&Pubkey) -> bool>(
+            accounts: &[(Pubkey, solana_sdk::account::Account)],
+            belongs: F,
+        ) -> Result<Vec<(Pubkey, crate::accounts::Member)>, ClientError> {
+            let mut members = vec![];
+            for (address, account) in accounts {
+                if account.data.len() as u64 != *crate::accounts::member::SIZE
+                    || !account.data.starts_with(&crate::accounts::member::DISCRIMINATOR)
+                {
+                    continue;
+                }
+                let m = crate::accounts::Member::unpack(&account.data)
+                    .map_err(|e| ClientError::RawError(e.to_string()))?;
+                if m.initialized && belongs(&m.entity) {
+                    members.push((*address, m));
+                }
+            }
+            Ok(members)
+        }
+
+        /// Reads the member, its entity and their registrar with a single
+        /// getMultipleAccounts request, erroring if they aren't linked to
+        /// each other.
+        pub fn member_snapshot(
+            &self,
+            registrar: &Pubkey,
+            entity: &Pubkey,
+            member: &Pubkey,
+        ) -> Result<MemberSnapshot, ClientError> {
+            let addresses = [*registrar, *entity, *member];
+            let accounts = self
+                .rpc()
+                .get_multiple_accounts_with_commitment(&addresses, CommitmentConfig::recent())?
+                .value;
+            let data = |idx: usize| -> Result<&[u8], ClientError> {
+                accounts[idx]
+                    .as_ref()
+                    .map(|account| account.data.as_slice())
+                    .ok_or_else(|| {
+                        ClientError::RawError(format!("account not found: {}", addresses[idx]))
+                    })
+            };
+
+            let registrar_account = crate::accounts::Registrar::unpack(data(0)?)
+                .map_err(|e| ClientError::RawError(e.to_string()))?;
+            let entity_account = crate::accounts::Entity::unpack(data(1)?)
+                .map_err(|e| ClientError::RawError(e.to_string()))?;
+            let member_account = crate::accounts::Member::unpack(data(2)?)
+                .map_err(|e| ClientError::RawError(e.to_string()))?;
+
+            if entity_account.registrar != *registrar {
+                return Err(ClientError::RawError(format!(
+                    "entity {} isn't in registrar {}",
+                    entity, registrar
+                )));
+            }
+            if member_account.entity != *entity {
+                return Err(ClientError::RawError(format!(
+                    "member {} isn't in entity {}",
+                    member, entity
+                )));
+            }
+
+            Ok(MemberSnapshot {
+                registrar: registrar_account,
+                entity: entity_account,
+                member: member_account,
+            })
         }
     }
 }
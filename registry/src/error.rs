@@ -16,6 +16,32 @@ pub enum RegistryErrorCode {
     WrongSerialization = 1,
     NotReadySeeNextMajorVersion = 2,
     MustBeDelegated = 3,
+    InvalidAccountOwner = 4,
+    NotInitialized = 5,
+    LeaderIsRegistrarAuthority = 6,
+    Unauthorized = 7,
+    InvalidClockSysvar = 8,
+    BeneficiaryBlocked = 9,
+    BlocklistFull = 10,
+    EntityRegistrarMismatch = 11,
+    InvalidCapabilityId = 12,
+    Paused = 13,
+    MemberNotEmpty = 14,
+    EntityNotEmpty = 15,
+    InvalidMetadata = 16,
+    AlreadyInitialized = 17,
+    MemberEntityMismatch = 18,
+    SameEntity = 19,
+    InvalidMultisig = 20,
+    InvalidBeneficiary = 21,
+    Overflow = 22,
+    InsufficientBalance = 23,
+    InvalidTokenProgram = 24,
+    InvalidCapabilityFee = 25,
+    CapabilitiesMigrated = 26,
+    InvalidCapabilityRegistry = 27,
+    Underflow = 28,
+    StaleEntity = 29,
     Unknown = 1000,
 }
 
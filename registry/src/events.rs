@@ -0,0 +1,220 @@
+//! Structured events logged by the program on every state transition.
+//!
+//! Each event is serialized with the same encoding as the accounts and
+//! logged, base64 encoded, after `EVENT_PREFIX`. Clients turn transaction
+//! logs back into events with `decode_logs`, which only trusts lines logged
+//! while the registry program itself is executing.
+
+use serde::{Deserialize, Serialize};
+use serum_common::pack::ProgramError;
+use solana_client_gen::solana_sdk::pubkey::Pubkey;
+
+/// Marks a program log line as an encoded event.
+pub const EVENT_PREFIX: &str = "registry-event: ";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum RegistryEvent {
+    RegistrarInitialized {
+        registrar: Pubkey,
+        authority: Pubkey,
+    },
+    RegistrarUpdated {
+        registrar: Pubkey,
+    },
+    RegistrarPaused {
+        registrar: Pubkey,
+        paused: bool,
+    },
+    CapabilityRegistered {
+        registrar: Pubkey,
+        capability_id: u8,
+        fee_bps: u32,
+    },
+    CapabilityRegistryCreated {
+        registrar: Pubkey,
+        capability_registry: Pubkey,
+    },
+    PendingAuthoritySet {
+        registrar: Pubkey,
+        pending_authority: Pubkey,
+    },
+    AuthorityAccepted {
+        registrar: Pubkey,
+        authority: Pubkey,
+    },
+    BeneficiaryBlocked {
+        registrar: Pubkey,
+        beneficiary: Pubkey,
+    },
+    BeneficiaryUnblocked {
+        registrar: Pubkey,
+        beneficiary: Pubkey,
+    },
+    EntityCreated {
+        entity: Pubkey,
+        registrar: Pubkey,
+        leader: Pubkey,
+    },
+    EntityUpdated {
+        entity: Pubkey,
+        leader: Pubkey,
+        capabilities: u64,
+    },
+    CapabilitiesAdded {
+        entity: Pubkey,
+        capabilities: u64,
+    },
+    CapabilitiesRemoved {
+        entity: Pubkey,
+        capabilities: u64,
+    },
+    Heartbeat {
+        entity: Pubkey,
+        slot: u64,
+    },
+    EntityClosed {
+        entity: Pubkey,
+    },
+    MetadataUpdated {
+        entity: Pubkey,
+        metadata: Pubkey,
+    },
+    MultisigCreated {
+        multisig: Pubkey,
+        m: u8,
+    },
+    MemberJoined {
+        member: Pubkey,
+        entity: Pubkey,
+        beneficiary: Pubkey,
+    },
+    DelegateSet {
+        member: Pubkey,
+        delegate: Pubkey,
+    },
+    BeneficiarySet {
+        member: Pubkey,
+        beneficiary: Pubkey,
+    },
+    MemberSwitched {
+        member: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+    },
+    MemberClosed {
+        member: Pubkey,
+    },
+    AccountMigrated {
+        account: Pubkey,
+        version: u8,
+    },
+}
+
+/// Returns the log line for the given event.
+pub fn encode(event: &RegistryEvent) -> Result<String, ProgramError> {
+    let bytes = serum_common::pack::to_bytes(event)?;
+    Ok(format!("{}{}", EVENT_PREFIX, base64::encode(bytes)))
+}
+
+/// Prefix the runtime adds to every line logged by a program.
+const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+
+/// Decodes the event in the given log line, if there is one. The line must be
+/// exactly as reported by the runtime, i.e., "Program log: " followed by the
+/// encoded event.
+pub fn decode(log: &str) -> Option<RegistryEvent> {
+    let prefix = format!("{}{}", PROGRAM_LOG_PREFIX, EVENT_PREFIX);
+    if !log.starts_with(&prefix) {
+        return None;
+    }
+    let bytes = base64::decode(&log[prefix.len()..]).ok()?;
+    serum_common::pack::from_bytes(&bytes).ok()
+}
+
+/// Decodes all the events logged by the registry `program_id` in a
+/// transaction's logs, in order. Lines logged by any other program, including
+/// programs invoked by or invoking the registry, are skipped, so that they
+/// can't forge events.
+pub fn decode_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<RegistryEvent> {
+    let invoke = format!("Program {} invoke [", program_id);
+    let mut frames: Vec<bool> = vec![];
+    let mut events = vec![];
+    for log in logs {
+        let log = log.as_ref();
+        if log.starts_with(PROGRAM_LOG_PREFIX) {
+            if frames.last() == Some(&true) {
+                if let Some(event) = decode(log) {
+                    events.push(event);
+                }
+            }
+        } else if log.contains(" invoke [") {
+            frames.push(log.starts_with(&invoke));
+        } else if log.ends_with(" success") || log.contains(" failed: ") {
+            frames.pop();
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let event = RegistryEvent::EntityCreated {
+            entity: Pubkey::new_rand(),
+            registrar: Pubkey::new_rand(),
+            leader: Pubkey::new_rand(),
+        };
+        let log = encode(&event).unwrap();
+        assert!(log.starts_with(EVENT_PREFIX));
+        assert_eq!(decode(&format!("Program log: {}", log)), Some(event));
+    }
+
+    #[test]
+    fn decode_rejects_unprefixed_lines() {
+        let log = encode(&RegistryEvent::EntityClosed {
+            entity: Pubkey::new_rand(),
+        })
+        .unwrap();
+        assert_eq!(decode(&log), None);
+        assert_eq!(decode(&format!("Program log: spoof {}", log)), None);
+    }
+
+    #[test]
+    fn decode_program_logs() {
+        let program_id = Pubkey::new_rand();
+        let other_program_id = Pubkey::new_rand();
+        let heartbeat = RegistryEvent::Heartbeat {
+            entity: Pubkey::new_rand(),
+            slot: 5,
+        };
+        let closed = RegistryEvent::EntityClosed {
+            entity: Pubkey::new_rand(),
+        };
+        let forged = RegistryEvent::EntityClosed {
+            entity: Pubkey::new_rand(),
+        };
+        let logs = vec![
+            format!("Program log: {}", encode(&forged).unwrap()),
+            format!("Program {} invoke [1]", program_id),
+            format!("Program log: {}", encode(&heartbeat).unwrap()),
+            "Program log: handler: heartbeat".to_string(),
+            format!("Program log: {}not base64", EVENT_PREFIX),
+            format!("Program log: Program {} success", program_id),
+            format!("Program {} invoke [2]", other_program_id),
+            format!("Program log: {}", encode(&forged).unwrap()),
+            format!("Program {} success", other_program_id),
+            format!("Program log: {}", encode(&closed).unwrap()),
+            format!("Program {} success", program_id),
+            format!("Program {} invoke [1]", other_program_id),
+            format!("Program log: {}", encode(&forged).unwrap()),
+            format!(
+                "Program {} failed: custom program error: 0x1",
+                other_program_id
+            ),
+        ];
+        assert_eq!(decode_logs(&program_id, &logs), vec![heartbeat, closed]);
+    }
+}
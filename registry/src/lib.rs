@@ -26,32 +26,41 @@ pub mod instruction {
             authority: Pubkey,
             /// Number of slots that must pass for a withdrawal to complete.
             withdrawal_timelock: u64,
+            /// Forbids the authority from creating an entity it leads.
+            separate_leader_and_authority: bool,
         },
         /// RegisterCapability registers a node capability for reward collection,
-        /// or overwrites an existing capability (e.g., on fee change).
+        /// or overwrites an existing capability (e.g., on fee change). Fails
+        /// once the registrar has a CapabilityRegistry, after which
+        /// SetCapability is used instead.
         ///
         /// Accounts:
         ///
         /// 0. `[signer]`   Registrar authority.
         /// 1. `[writable]` Registrar instance.
         RegisterCapability {
-            /// The identifier to assign this capability.
+            /// The identifier to assign this capability. Must be less than
+            /// `registrar::MAX_CAPABILITIES`.
             capability_id: u8,
             /// Capability fee in bps. The amount to pay a node for an instruction fulfilling
             /// this duty.
             capability_fee_bps: u32,
         },
         /// CreateEntity initializes the new "node" with the Registry, designated "inactive".
+        /// Fails while the registrar is paused.
         ///
         /// Accounts:
         ///
         /// 0. `[writable]` Entity account.
         /// 1. `[signer]`   Leader of the node.
-        /// 2. `[]`         Rent sysvar.
+        /// 2. `[]`         Registrar instance.
+        /// 3. `[]`         Rent sysvar.
+        /// 4. `[]`         CapabilityRegistry of the registrar, only if it
+        ///                 has one.
         CreateEntity {
             /// The Serum ecosystem duties a Node performs to earn extra performance
             /// based rewards, for example, cranking.
-            capabilities: u32,
+            capabilities: u64,
             /// Type of governance backing the `Entity`. For simplicity in the first version,
             /// all `nodes` will be `delegated-staked`, which means the `node-leader`
             /// will execute governance decisions.
@@ -62,15 +71,23 @@ pub mod instruction {
         /// Accounts:
         ///
         /// 0. `[writable]` Entity account.
-        /// 1. `[signer]`   Leader of the entity.
-        UpdateEntity { leader: Pubkey, capabilities: u32 },
-        /// Joins the entity by creating a membership account.
+        /// 1. `[signer]`   Leader of the entity. If the leader is a Multisig,
+        ///                 the Multisig account, not signing.
+        /// 2. `[]`         Registrar the entity was created with.
+        /// 3. `[]`         CapabilityRegistry of the registrar. Omitted if it
+        ///                 has none, in which case the signers start at 3.
+        /// 4.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        UpdateEntity { leader: Pubkey, capabilities: u64 },
+        /// Joins the entity by creating a membership account. Fails while the
+        /// registrar is paused.
         ///
         /// Accounts:
         ///
         /// 0. `[writable]` Member account being created.
-        /// 1. `[]`         Entity account to stake to.
+        /// 1. `[writable]` Entity account to stake to.
+        /// 2. `[]`         Registrar the entity was created with.
         /// 3. `[]`         Rent sysvar.
+        /// 4. `[]`         Clock sysvar.
         JoinEntity {
             /// The owner of this entity account. Must sign off when staking and
             /// withdrawing.
@@ -79,7 +96,6 @@ pub mod instruction {
             /// behalf.
             delegate: Pubkey,
         },
-        // TODO: update member to change delegate access.
         /// Deposits funds into the staking pool on behalf Member account of
         /// the Member account, issuing staking pool tokens as proof of deposit.
         ///
@@ -144,9 +160,255 @@ pub mod instruction {
             /// The amount to deposit.
             amount: u64,
         },
+        /// Records the current slot on the entity as the leader's latest
+        /// attestation that it's fulfilling its node duties.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Entity account.
+        /// 1. `[signer]`   Leader of the entity. If the leader is a Multisig,
+        ///                 the Multisig account, not signing.
+        /// 2. `[]`         Clock sysvar.
+        /// 3.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        Heartbeat,
+        /// Sets the given capability bits on the entity, leaving all others
+        /// untouched.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Entity account.
+        /// 1. `[signer]`   Leader of the entity. If the leader is a Multisig,
+        ///                 the Multisig account, not signing.
+        /// 2. `[]`         Registrar the entity was created with.
+        /// 3. `[]`         CapabilityRegistry of the registrar. Omitted if it
+        ///                 has none, in which case the signers start at 3.
+        /// 4.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        AddCapabilities { capabilities: u64 },
+        /// Clears the given capability bits on the entity, leaving all others
+        /// untouched.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Entity account.
+        /// 1. `[signer]`   Leader of the entity. If the leader is a Multisig,
+        ///                 the Multisig account, not signing.
+        /// 2. `[]`         Registrar the entity was created with.
+        /// 3. `[]`         CapabilityRegistry of the registrar. Omitted if it
+        ///                 has none, in which case the signers start at 3.
+        /// 4.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        RemoveCapabilities { capabilities: u64 },
+        /// Prevents the beneficiary from joining any of the registrar's
+        /// entities. Existing memberships are unaffected.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        BlockBeneficiary { beneficiary: Pubkey },
+        /// Removes the beneficiary from the registrar's blocklist.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        UnblockBeneficiary { beneficiary: Pubkey },
+        /// Proposes a new registrar authority, replacing any previous
+        /// proposal. Control doesn't change hands until the proposed
+        /// authority invokes AcceptAuthority.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        SetPendingAuthority { pending_authority: Pubkey },
+        /// Completes an authority transfer started by SetPendingAuthority.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Pending registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        AcceptAuthority,
+        /// Halts entity creation and joining until Unpause is invoked.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        Pause,
+        /// Resumes normal operation after a Pause.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        Unpause,
+        /// Updates the registrar's configuration. Fields set to `None` are
+        /// left unchanged.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[writable]` Registrar instance.
+        UpdateRegistrar {
+            /// Number of slots that must pass for a withdrawal to complete.
+            withdrawal_timelock: Option<u64>,
+            /// Forbids the authority from creating an entity it leads.
+            separate_leader_and_authority: Option<bool>,
+            /// Slots after its last heartbeat before an entity is stale.
+            heartbeat_timeout: Option<u64>,
+        },
+        /// Closes a Member account with no stake, sending its rent to the
+        /// given account.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Member account to close.
+        /// 1. `[signer]`   Beneficiary of the Member account.
+        /// 2. `[writable]` Account to send the lamports to.
+        /// 3. `[writable]` Entity the member belongs to.
+        CloseMember,
+        /// Closes an Entity account with no stake and no members, along with
+        /// its EntityMetadata if one was created, sending their rent to the
+        /// given account.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Entity account to close.
+        /// 1. `[signer]`   Leader of the entity *or* the registrar authority.
+        ///                 If the leader is a Multisig, the Multisig account,
+        ///                 not signing.
+        /// 2. `[]`         Registrar the entity was created with.
+        /// 3. `[writable]` Account to send the lamports to.
+        /// 4. `[writable]` EntityMetadata address derived from the entity,
+        ///                 whether or not it was created.
+        /// 5.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        CloseEntity,
+        /// Creates the EntityMetadata account advertising the entity, at the
+        /// address `addresses::metadata` derives from the entity.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` EntityMetadata account to create.
+        /// 1. `[signer]`   Leader of the entity. If the leader is a Multisig,
+        ///                 the Multisig account, not signing.
+        /// 2. `[]`         Entity the metadata describes.
+        /// 3. `[writable, signer]` Payer funding the account's rent.
+        /// 4. `[]`         Rent sysvar.
+        /// 5. `[]`         System program.
+        /// 6.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        CreateMetadata {
+            name: String,
+            url: String,
+            description: String,
+        },
+        /// Updates an entity's metadata. Fields set to `None` are left
+        /// unchanged.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` EntityMetadata account.
+        /// 1. `[signer]`   Leader of the entity. If the leader is a Multisig,
+        ///                 the Multisig account, not signing.
+        /// 2. `[]`         Entity the metadata describes.
+        /// 3.. `[signer]`  Multisig signers, if the leader is a Multisig.
+        UpdateMetadata {
+            name: Option<String>,
+            url: Option<String>,
+            description: Option<String>,
+        },
+        /// Assigns or replaces the delegate of a Member account. Fails if
+        /// the member has any stake, since the outgoing delegate may have
+        /// deposited it, or while the registrar is paused.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Member account.
+        /// 1. `[signer]`   Beneficiary of the Member account.
+        /// 2. `[]`         Entity the member belongs to.
+        /// 3. `[]`         Registrar of the entity.
+        SetDelegate { delegate: Pubkey },
+        /// Removes the delegate of a Member account, with the same checks
+        /// as SetDelegate.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Member account.
+        /// 1. `[signer]`   Beneficiary of the Member account.
+        /// 2. `[]`         Entity the member belongs to.
+        /// 3. `[]`         Registrar of the entity.
+        RevokeDelegate,
+        /// Moves a Member, along with its stake, from its current entity to
+        /// another entity of the same registrar, without withdrawing.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Member account.
+        /// 1. `[signer]`   Beneficiary of the Member account.
+        /// 2. `[writable]` Entity the member currently belongs to.
+        /// 3. `[writable]` Entity to move the member to.
+        /// 4. `[]`         Registrar of both entities.
+        SwitchEntity,
+        /// Initializes an m-of-n Multisig, which can be used as an entity
+        /// leader in place of a single key.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Multisig account to initialize.
+        #[cfg_attr(feature = "client", create_account(*multisig::SIZE))]
+        CreateMultisig { m: u8, signers: Vec<Pubkey> },
+        /// Hands a Member account over to a new beneficiary. Fails if the
+        /// new beneficiary is blocked by the registrar, or while the
+        /// registrar is paused.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Member account.
+        /// 1. `[signer]`   Current beneficiary of the Member account.
+        /// 2. `[]`         Entity the member belongs to.
+        /// 3. `[]`         Registrar of the entity.
+        SetBeneficiary { beneficiary: Pubkey },
+        /// Creates the registrar's CapabilityRegistry, migrating every
+        /// capability with a nonzero fee in the registrar's fee table into
+        /// it. From then on, capabilities are registered with SetCapability.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` CapabilityRegistry account to initialize.
+        /// 1. `[signer]`   Registrar authority.
+        /// 2. `[writable]` Registrar instance.
+        #[cfg_attr(feature = "client", create_account(capability_registry::SIZE))]
+        CreateCapabilityRegistry,
+        /// Registers a capability in the registrar's CapabilityRegistry, or
+        /// overwrites an existing one.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[signer]`   Registrar authority.
+        /// 1. `[]`         Registrar instance.
+        /// 2. `[writable]` CapabilityRegistry of the registrar.
+        SetCapability {
+            /// The identifier to assign this capability. Must be less than
+            /// `capability_registry::MAX_CAPABILITIES`.
+            capability_id: u8,
+            /// Capability fee in bps.
+            capability_fee_bps: u32,
+            /// Hash of the capability's human readable label.
+            label_hash: [u8; 32],
+        },
+        /// Upgrades a Registrar, Entity, or Member account written with an
+        /// older layout to the current one, in place. Anyone can migrate an
+        /// account, since the upgrade doesn't change its state. Accounts
+        /// are also upgraded by any instruction mutating them.
+        ///
+        /// Accounts:
+        ///
+        /// 0. `[writable]` Account to migrate.
+        MigrateAccount,
     }
 }
 
+#[cfg(feature = "client")]
+pub mod caching_client;
 #[cfg(feature = "client")]
 pub mod client_ext;
 #[cfg(feature = "client")]
@@ -155,6 +417,8 @@ pub use client_ext::client;
 pub use client_ext::instruction;
 
 pub mod accounts;
+pub mod addresses;
 pub mod error;
+pub mod events;
 
 serum_common::packable!(crate::instruction::RegistryInstruction);
@@ -0,0 +1,91 @@
+use common::Fixture;
+use serum_registry::accounts::Entity;
+use serum_registry::caching_client::CachingClient;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::Signer;
+use std::time::Duration;
+
+mod common;
+
+#[test]
+fn caching_client() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    let client = CachingClient::new(client, Duration::from_secs(600));
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+
+    // Prime the cache.
+    assert_eq!(client.entity(&entity).unwrap().capabilities, 1);
+    assert_eq!(
+        client.registrar(&registrar).unwrap().authority,
+        registrar_authority.pubkey()
+    );
+
+    // Mutate through the inner client, which doesn't touch the cache.
+    client
+        .inner()
+        .update_entity_with_signers(
+            &[&leader, client.inner().payer()],
+            &accounts,
+            leader.pubkey(),
+            1 | 2,
+        )
+        .unwrap();
+
+    // Reads are served from the cache, not the cluster.
+    assert_eq!(client.entity(&entity).unwrap().capabilities, 1);
+    let entity_account: Entity =
+        serum_common::client::rpc::account_unpacked(client.inner().rpc(), &entity);
+    assert_eq!(entity_account.capabilities, 1 | 2);
+
+    // Mutating through the cache invalidates the written accounts.
+    client
+        .mutate(&[entity], |c| {
+            c.update_entity_with_signers(&[&leader, c.payer()], &accounts, leader.pubkey(), 4)
+        })
+        .unwrap();
+    assert_eq!(client.entity(&entity).unwrap().capabilities, 4);
+}
+
+#[test]
+fn caching_client_expires() {
+    let Fixture {
+        client,
+        registrar,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    // A zero TTL always reads through.
+    let client = CachingClient::new(client, Duration::from_secs(0));
+    assert_eq!(client.entity(&entity).unwrap().capabilities, 1);
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    client
+        .inner()
+        .update_entity_with_signers(
+            &[&leader, client.inner().payer()],
+            &accounts,
+            leader.pubkey(),
+            1 | 2,
+        )
+        .unwrap();
+
+    assert_eq!(client.entity(&entity).unwrap().capabilities, 1 | 2);
+}
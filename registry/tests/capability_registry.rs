@@ -0,0 +1,111 @@
+use common::Fixture;
+use serum_registry::accounts::{CapabilityRegistry, Entity, Registrar};
+use serum_registry::error::RegistryErrorCode;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::Signer;
+
+mod common;
+
+#[test]
+fn migrate_and_set_capabilities() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    let signers = [&registrar_authority, client.payer()];
+    let governance_accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .register_capability_with_signers(&signers, &governance_accounts, 3, 1234)
+        .unwrap();
+
+    // Legacy fees are migrated into the new registry.
+    let capability_registry =
+        common::create_capability_registry(&client, &registrar_authority, registrar)
+            .unwrap()
+            .pubkey();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert_eq!(registrar_account.capability_registry, capability_registry);
+    let registry: CapabilityRegistry =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &capability_registry);
+    assert_eq!(registry.registrar, registrar);
+    assert_eq!(registry.capabilities.len(), 1);
+    assert_eq!(registry.get(3).unwrap().fee_bps, 1234);
+
+    // The fee table is frozen once migrated.
+    assert!(client
+        .register_capability_with_signers(&signers, &governance_accounts, 4, 1)
+        .is_err());
+
+    // Ids past the legacy 32 slots can be registered and set on entities.
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(capability_registry, false),
+    ];
+    client
+        .set_capability_with_signers(&signers, &accounts, 40, 50, [7; 32])
+        .unwrap();
+    let registry: CapabilityRegistry =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &capability_registry);
+    assert_eq!(registry.capabilities.len(), 2);
+    assert_eq!(registry.get(40).unwrap().fee_bps, 50);
+    assert_eq!(registry.get(40).unwrap().label_hash, [7; 32]);
+
+    assert!(client
+        .set_capability_with_signers(&signers, &accounts, 64, 50, [7; 32])
+        .is_err());
+
+    let leader_signers = [&leader, client.payer()];
+    let entity_accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(capability_registry, false),
+    ];
+    client
+        .add_capabilities_with_signers(&leader_signers, &entity_accounts, 1 << 40)
+        .unwrap();
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.capabilities & (1 << 40), 1 << 40);
+
+    // Only registered ids can be set, and the registry must be given.
+    let err = client
+        .add_capabilities_with_signers(&leader_signers, &entity_accounts, 1 << 41)
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::InvalidCapabilityId.into())
+    );
+    assert!(client
+        .add_capabilities_with_signers(&leader_signers, &entity_accounts[..3], 1 << 40)
+        .is_err());
+
+    // Fee schedules go to the registry once migrated, keeping labels.
+    client
+        .set_fee_schedule(&registrar, &registrar_authority, &[(40, 60), (63, 70)])
+        .unwrap();
+    let registry: CapabilityRegistry =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &capability_registry);
+    assert_eq!(registry.capabilities.len(), 3);
+    assert_eq!(registry.get(40).unwrap().fee_bps, 60);
+    assert_eq!(registry.get(40).unwrap().label_hash, [7; 32]);
+    assert_eq!(registry.get(63).unwrap().fee_bps, 70);
+    assert_eq!(registry.get(63).unwrap().label_hash, [0; 32]);
+    assert!(client
+        .set_fee_schedule(&registrar, &registrar_authority, &[(64, 1)])
+        .is_err());
+
+    // A registrar only ever has one registry.
+    assert!(common::create_capability_registry(&client, &registrar_authority, registrar).is_err());
+}
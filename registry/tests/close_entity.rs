@@ -0,0 +1,195 @@
+use common::Fixture;
+use rand::rngs::OsRng;
+use serum_registry::accounts::{Entity, StakeKind};
+use serum_registry::addresses;
+use serum_registry::client::Client;
+use serum_registry::error::RegistryErrorCode;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+
+mod common;
+
+#[test]
+fn leader_can_close_entity() {
+    let client = serum_common_tests::client::<Client>();
+    let (_registrar_authority, registrar) = common::registrar(&client);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+    let destination = Pubkey::new_rand();
+    let metadata = addresses::metadata(&entity, client.program()).unwrap().0;
+
+    // Someone other than the leader or authority.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(client.payer().pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(metadata, false),
+    ];
+    assert!(client.close_entity(&accounts).is_err());
+
+    let entity_lamports = client.rpc().get_balance(&entity).unwrap();
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(metadata, false),
+    ];
+    client
+        .close_entity_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+
+    assert!(client.rpc().get_account(&entity).is_err());
+    assert_eq!(
+        client.rpc().get_balance(&destination).unwrap(),
+        entity_lamports
+    );
+}
+
+#[test]
+fn registrar_authority_can_close_entity() {
+    let client = serum_common_tests::client::<Client>();
+    let (registrar_authority, registrar) = common::registrar(&client);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+    let destination = Pubkey::new_rand();
+    let metadata = addresses::metadata(&entity, client.program()).unwrap().0;
+
+    // Entity doesn't belong to the given registrar.
+    let (_other_authority, other_registrar) = common::registrar(&client);
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new_readonly(other_registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(metadata, false),
+    ];
+    assert!(client
+        .close_entity_with_signers(&[&registrar_authority, client.payer()], &accounts)
+        .is_err());
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(metadata, false),
+    ];
+    client
+        .close_entity_with_signers(&[&registrar_authority, client.payer()], &accounts)
+        .unwrap();
+
+    assert!(client.rpc().get_account(&entity).is_err());
+}
+
+#[test]
+fn closing_entity_closes_metadata() {
+    let client = serum_common_tests::client::<Client>();
+    let (_registrar_authority, registrar) = common::registrar(&client);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+    let (_tx_sig, metadata) = client
+        .create_metadata_derived(
+            &leader,
+            entity,
+            "entity".to_string(),
+            String::new(),
+            String::new(),
+        )
+        .unwrap();
+    let destination = Pubkey::new_rand();
+
+    // Metadata that isn't the entity's.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(Pubkey::new_rand(), false),
+    ];
+    let err = client
+        .close_entity_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::InvalidMetadata.into())
+    );
+
+    let lamports =
+        client.rpc().get_balance(&entity).unwrap() + client.rpc().get_balance(&metadata).unwrap();
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(metadata, false),
+    ];
+    client
+        .close_entity_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+
+    assert!(client.rpc().get_account(&metadata).is_err());
+    assert!(client.entity_metadata(&entity).unwrap().is_none());
+    assert_eq!(client.rpc().get_balance(&destination).unwrap(), lamports);
+}
+
+#[test]
+fn entity_with_members_cannot_be_closed() {
+    let Fixture {
+        client,
+        registrar,
+        leader,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+    let destination = Pubkey::new_rand();
+    let metadata = addresses::metadata(&entity, client.program()).unwrap().0;
+
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.members, 1);
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(metadata, false),
+    ];
+    let err = client
+        .close_entity_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::EntityNotEmpty.into())
+    );
+
+    // Once the last member leaves, the entity can be closed.
+    client
+        .close_member_with_signers(
+            &[&beneficiary, client.payer()],
+            &[
+                AccountMeta::new(member, false),
+                AccountMeta::new_readonly(beneficiary.pubkey(), true),
+                AccountMeta::new(destination, false),
+                AccountMeta::new(entity, false),
+            ],
+        )
+        .unwrap();
+    client
+        .close_entity_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+    assert!(client.rpc().get_account(&entity).is_err());
+}
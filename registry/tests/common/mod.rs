@@ -1,2 +1,154 @@
 // Work around for https://github.com/rust-lang/rust/issues/46379.
 #![allow(dead_code)]
+
+use rand::rngs::OsRng;
+use serum_registry::accounts::{member, StakeKind};
+use serum_registry::client::{Client, ClientError};
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::sysvar;
+
+// A fresh registrar, with an entity and a member of that entity, for tests
+// exercising a single instruction.
+pub struct Fixture {
+    pub client: Client,
+    pub registrar_authority: Keypair,
+    pub registrar: Pubkey,
+    pub leader: Keypair,
+    pub entity: Pubkey,
+    pub beneficiary: Keypair,
+    pub member: Pubkey,
+}
+
+pub fn fixture() -> Fixture {
+    let client = serum_common_tests::client::<Client>();
+
+    let (registrar_authority, registrar) = registrar(&client);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    let beneficiary = Keypair::generate(&mut OsRng);
+    let member = join_entity(&client, entity, registrar, beneficiary.pubkey());
+
+    Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        leader,
+        entity,
+        beneficiary,
+        member,
+    }
+}
+
+// Initializes a registrar with a fresh authority, returning the authority
+// and the registrar's address.
+pub fn registrar(client: &Client) -> (Keypair, Pubkey) {
+    registrar_with_separate_leader_and_authority(client, false)
+}
+
+pub fn registrar_with_separate_leader_and_authority(
+    client: &Client,
+    separate_leader_and_authority: bool,
+) -> (Keypair, Pubkey) {
+    let registrar_authority = Keypair::generate(&mut OsRng);
+    let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
+    let (_tx_sig, registrar) = client
+        .create_account_and_initialize(
+            &accounts,
+            registrar_authority.pubkey(),
+            1234,
+            separate_leader_and_authority,
+        )
+        .unwrap();
+    (registrar_authority, registrar.pubkey())
+}
+
+// Joins the entity with a member account at a random address. Unlike
+// `join_entity_derived`, which derives the address from the payer, this can
+// be used any number of times with the same payer.
+pub fn join_entity(
+    client: &Client,
+    entity: Pubkey,
+    registrar: Pubkey,
+    beneficiary: Pubkey,
+) -> Pubkey {
+    try_join_entity(client, entity, registrar, beneficiary).unwrap()
+}
+
+pub fn try_join_entity(
+    client: &Client,
+    entity: Pubkey,
+    registrar: Pubkey,
+    beneficiary: Pubkey,
+) -> Result<Pubkey, ClientError> {
+    let member_kp = Keypair::generate(&mut OsRng);
+    let lamports = client
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(*member::SIZE as usize)?;
+    let create_acc_instr = system_instruction::create_account(
+        &client.payer().pubkey(),
+        &member_kp.pubkey(),
+        lamports,
+        *member::SIZE,
+        client.program(),
+    );
+
+    let accounts = [
+        AccountMeta::new(member_kp.pubkey(), false),
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    let join_instr = serum_registry::instruction::join_entity(
+        *client.program(),
+        &accounts,
+        beneficiary,
+        Pubkey::new_from_array([0; 32]),
+    );
+
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(
+        &[create_acc_instr, join_instr],
+        Some(&client.payer().pubkey()),
+        &[client.payer(), &member_kp],
+        recent_hash,
+    );
+    client
+        .rpc()
+        .send_and_confirm_transaction_with_spinner_and_config(
+            &tx,
+            client.options().commitment,
+            client.options().tx,
+        )?;
+
+    Ok(member_kp.pubkey())
+}
+
+// Creates the registrar's CapabilityRegistry, migrating its fee table.
+pub fn create_capability_registry(
+    client: &Client,
+    registrar_authority: &Keypair,
+    registrar: Pubkey,
+) -> Result<Keypair, ClientError> {
+    let capability_registry = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new(capability_registry.pubkey(), false),
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    let signers = [client.payer(), &capability_registry, registrar_authority];
+    let new_account = Keypair::from_bytes(&capability_registry.to_bytes()).unwrap();
+    client
+        .create_account_and_create_capability_registry_with_signers(
+            new_account,
+            &signers,
+            &accounts,
+        )
+        .map(|(_tx_sig, capability_registry)| capability_registry)
+}
@@ -0,0 +1,164 @@
+use common::Fixture;
+use rand::rngs::OsRng;
+use serum_registry::accounts::Entity;
+use serum_registry::error::RegistryErrorCode;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::sysvar;
+
+mod common;
+
+#[test]
+fn heartbeat() {
+    let Fixture {
+        client,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.last_heartbeat_slot, 0);
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    client
+        .heartbeat_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert!(entity_account.last_heartbeat_slot > 0);
+
+    // A later heartbeat records the later slot.
+    serum_common_tests::wait_for_slot(&client, entity_account.last_heartbeat_slot + 2);
+    client
+        .heartbeat_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+    let last_heartbeat_slot = entity_account.last_heartbeat_slot;
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert!(entity_account.last_heartbeat_slot > last_heartbeat_slot);
+
+    // Only the leader can attest.
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    assert!(client
+        .heartbeat_with_signers(&[&imposter, client.payer()], &accounts)
+        .is_err());
+}
+
+#[test]
+fn stale_entity() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    let heartbeat_timeout = 30;
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .update_registrar_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            None,
+            None,
+            Some(heartbeat_timeout),
+        )
+        .unwrap();
+
+    // Fresh.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    client
+        .heartbeat_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+    common::join_entity(&client, entity, registrar, Pubkey::new_rand());
+
+    // Stale.
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    serum_common_tests::wait_for_slot(
+        &client,
+        entity_account.last_heartbeat_slot + heartbeat_timeout + 1,
+    );
+    let err = common::try_join_entity(&client, entity, registrar, Pubkey::new_rand()).unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::StaleEntity.into())
+    );
+
+    // Fresh again after the next heartbeat.
+    client
+        .heartbeat_with_signers(&[&leader, client.payer()], &accounts)
+        .unwrap();
+    common::join_entity(&client, entity, registrar, Pubkey::new_rand());
+}
+
+#[test]
+fn add_and_remove_capabilities() {
+    let Fixture {
+        client,
+        registrar,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    let signers = [&leader, client.payer()];
+
+    client
+        .add_capabilities_with_signers(&signers, &accounts, 2 | 4)
+        .unwrap();
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.capabilities, 1 | 2 | 4);
+
+    client
+        .remove_capabilities_with_signers(&signers, &accounts, 1)
+        .unwrap();
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.capabilities, 2 | 4);
+
+    // Removing bits that aren't set is a no-op.
+    client
+        .remove_capabilities_with_signers(&signers, &accounts, 8)
+        .unwrap();
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.capabilities, 2 | 4);
+
+    // Bits past the registrar's fee table aren't capabilities.
+    let err = client
+        .add_capabilities_with_signers(&signers, &accounts, 1 << 32)
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::InvalidCapabilityId.into())
+    );
+    let err = client
+        .remove_capabilities_with_signers(&signers, &accounts, 1 << 32)
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::InvalidCapabilityId.into())
+    );
+}
@@ -0,0 +1,176 @@
+use rand::rngs::OsRng;
+use serum_registry::accounts::{metadata, StakeKind};
+use serum_registry::addresses;
+use serum_registry::client::Client;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::{system_instruction, system_program, sysvar};
+
+mod common;
+
+#[test]
+fn create_and_update_metadata() {
+    let client = serum_common_tests::client::<Client>();
+    let (_authority, registrar) = common::registrar(&client);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    assert!(client.entity_metadata(&entity).unwrap().is_none());
+
+    // Create anywhere but the derived address.
+    let accounts = [
+        AccountMeta::new(Pubkey::new_rand(), false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new(client.payer().pubkey(), true),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    assert!(client
+        .create_metadata_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            "entity".to_string(),
+            String::new(),
+            String::new(),
+        )
+        .is_err());
+
+    // Create.
+    let (_tx_sig, metadata_addr) = client
+        .create_metadata_derived(
+            &leader,
+            entity,
+            "entity".to_string(),
+            "https://example.com".to_string(),
+            "contact: ops@example.com".to_string(),
+        )
+        .unwrap();
+    let (addr, m) = client.entity_metadata(&entity).unwrap().unwrap();
+    assert_eq!(addr, metadata_addr);
+    assert_eq!(
+        metadata_addr,
+        addresses::metadata(&entity, client.program()).unwrap().0
+    );
+    assert!(m.initialized);
+    assert_eq!(m.entity, entity);
+    assert_eq!(m.name, "entity");
+    assert_eq!(m.url, "https://example.com");
+    assert_eq!(m.description, "contact: ops@example.com");
+
+    // Update by someone other than the leader.
+    let accounts = [
+        AccountMeta::new(metadata_addr, false),
+        AccountMeta::new_readonly(client.payer().pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+    ];
+    assert!(client
+        .update_metadata(&accounts, Some("stolen".to_string()), None, None)
+        .is_err());
+
+    // Update past the maximum length.
+    let accounts = [
+        AccountMeta::new(metadata_addr, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+    ];
+    assert!(client
+        .update_metadata_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            Some("n".repeat(metadata::MAX_NAME_LEN + 1)),
+            None,
+            None,
+        )
+        .is_err());
+
+    // Update.
+    client
+        .update_metadata_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            Some("renamed".to_string()),
+            None,
+            Some(String::new()),
+        )
+        .unwrap();
+    let (_addr, m) = client.entity_metadata(&entity).unwrap().unwrap();
+    assert_eq!(m.name, "renamed");
+    assert_eq!(m.url, "https://example.com");
+    assert_eq!(m.description, "");
+
+    // Can't be created twice.
+    let accounts = [
+        AccountMeta::new(metadata_addr, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new(client.payer().pubkey(), true),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    assert!(client
+        .create_metadata_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            "again".to_string(),
+            String::new(),
+            String::new(),
+        )
+        .is_err());
+}
+
+#[test]
+fn create_metadata_at_funded_address() {
+    let client = serum_common_tests::client::<Client>();
+    let (_authority, registrar) = common::registrar(&client);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    // Anyone can fund the derived address before the leader creates it.
+    let address = addresses::metadata(&entity, client.program()).unwrap().0;
+    let instr = system_instruction::transfer(&client.payer().pubkey(), &address, 1);
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instr],
+        Some(&client.payer().pubkey()),
+        &[client.payer()],
+        recent_hash,
+    );
+    client
+        .rpc()
+        .send_and_confirm_transaction_with_spinner_and_config(
+            &tx,
+            client.options().commitment,
+            client.options().tx,
+        )
+        .unwrap();
+
+    client
+        .create_metadata_derived(
+            &leader,
+            entity,
+            "entity".to_string(),
+            String::new(),
+            String::new(),
+        )
+        .unwrap();
+    let (addr, m) = client.entity_metadata(&entity).unwrap().unwrap();
+    assert_eq!(addr, address);
+    assert_eq!(m.name, "entity");
+    let account = client.rpc().get_account(&address).unwrap();
+    assert_eq!(account.owner, *client.program());
+    assert_eq!(
+        account.lamports,
+        client
+            .rpc()
+            .get_minimum_balance_for_rent_exemption(metadata::SIZE as usize)
+            .unwrap()
+    );
+}
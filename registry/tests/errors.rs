@@ -0,0 +1,630 @@
+use common::Fixture;
+use rand::rngs::OsRng;
+use serum_registry::accounts::entity;
+use serum_registry::accounts::registrar::{MAX_BLOCKLIST, MAX_CAPABILITY_FEE_BPS};
+use serum_registry::accounts::StakeKind;
+use serum_registry::client::{Client, ClientError};
+use serum_registry::error::RegistryErrorCode;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::{system_instruction, system_program, sysvar};
+
+mod common;
+
+// Each test triggers a single error code on chain, asserting it's the one
+// returned, so that changes to the order or kind of access control checks
+// are caught.
+
+#[test]
+fn unauthorized() {
+    let Fixture { client, entity, .. } = common::fixture();
+
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    assert_error_code(
+        client.heartbeat_with_signers(&[&imposter, client.payer()], &accounts),
+        RegistryErrorCode::Unauthorized,
+    );
+}
+
+#[test]
+fn invalid_account_owner() {
+    let Fixture { client, leader, .. } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new(Pubkey::new_rand(), false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    assert_error_code(
+        client.heartbeat_with_signers(&[&leader, client.payer()], &accounts),
+        RegistryErrorCode::InvalidAccountOwner,
+    );
+}
+
+#[test]
+fn already_initialized() {
+    let Fixture {
+        client, registrar, ..
+    } = common::fixture();
+
+    // Re-initializing would hand the registrar to a new authority.
+    let accounts = [
+        AccountMeta::new(registrar, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+    ];
+    assert_error_code(
+        client.initialize(&accounts, client.payer().pubkey(), 1234, false),
+        RegistryErrorCode::AlreadyInitialized,
+    );
+}
+
+#[test]
+fn entity_already_initialized() {
+    let Fixture {
+        client,
+        registrar,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    // Re-creating the fixture's entity would hand it to a new leader.
+    let attacker = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(attacker.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+    ];
+    assert_error_code(
+        client.create_entity_with_signers(
+            &[&attacker, client.payer()],
+            &accounts,
+            1,
+            StakeKind::Delegated,
+        ),
+        RegistryErrorCode::AlreadyInitialized,
+    );
+    let entity_account: entity::Entity =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.leader, leader.pubkey());
+}
+
+#[test]
+fn member_already_initialized() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    // Re-joining would zero the fixture member's balances.
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    assert_error_code(
+        client.join_entity(
+            &accounts,
+            beneficiary.pubkey(),
+            Pubkey::new_from_array([0; 32]),
+        ),
+        RegistryErrorCode::AlreadyInitialized,
+    );
+}
+
+#[test]
+fn must_be_delegated() {
+    let Fixture {
+        client, registrar, ..
+    } = common::fixture();
+
+    let leader = Keypair::generate(&mut OsRng);
+    assert_error_code(
+        client.create_entity_derived(&leader, registrar, 1, StakeKind::Voting),
+        RegistryErrorCode::MustBeDelegated,
+    );
+}
+
+#[test]
+fn paused() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .pause_with_signers(&[&registrar_authority, client.payer()], &accounts)
+        .unwrap();
+
+    let leader = Keypair::generate(&mut OsRng);
+    assert_error_code(
+        client.create_entity_derived(&leader, registrar, 1, StakeKind::Delegated),
+        RegistryErrorCode::Paused,
+    );
+}
+
+#[test]
+fn invalid_capability_id() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert_error_code(
+        client.register_capability_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            32,
+            1234,
+        ),
+        RegistryErrorCode::InvalidCapabilityId,
+    );
+}
+
+#[test]
+fn invalid_capability_fee() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert_error_code(
+        client.register_capability_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            1,
+            MAX_CAPABILITY_FEE_BPS + 1,
+        ),
+        RegistryErrorCode::InvalidCapabilityFee,
+    );
+}
+
+#[test]
+fn capabilities_migrated() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    common::create_capability_registry(&client, &registrar_authority, registrar).unwrap();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert_error_code(
+        client.register_capability_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            1,
+            1,
+        ),
+        RegistryErrorCode::CapabilitiesMigrated,
+    );
+}
+
+#[test]
+fn invalid_capability_registry() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+    let other = common::fixture();
+
+    common::create_capability_registry(&client, &registrar_authority, registrar).unwrap();
+    let other_capability_registry = common::create_capability_registry(
+        &other.client,
+        &other.registrar_authority,
+        other.registrar,
+    )
+    .unwrap();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new(other_capability_registry.pubkey(), false),
+    ];
+    assert_error_code(
+        client.set_capability_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            1,
+            1,
+            [0; 32],
+        ),
+        RegistryErrorCode::InvalidCapabilityRegistry,
+    );
+}
+
+#[test]
+fn register_capability_requires_authority() {
+    let Fixture {
+        client, registrar, ..
+    } = common::fixture();
+
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert_error_code(
+        client.register_capability_with_signers(&[&imposter, client.payer()], &accounts, 1, 1234),
+        RegistryErrorCode::Unauthorized,
+    );
+}
+
+#[test]
+fn beneficiary_blocked() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        entity,
+        ..
+    } = common::fixture();
+
+    let blocked = Pubkey::new_rand();
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .block_beneficiary_with_signers(&[&registrar_authority, client.payer()], &accounts, blocked)
+        .unwrap();
+
+    assert_error_code(
+        common::try_join_entity(&client, entity, registrar, blocked),
+        RegistryErrorCode::BeneficiaryBlocked,
+    );
+}
+
+#[test]
+fn entity_registrar_mismatch() {
+    let Fixture { client, entity, .. } = common::fixture();
+    let Fixture {
+        registrar: other_registrar,
+        ..
+    } = common::fixture();
+
+    assert_error_code(
+        common::try_join_entity(&client, entity, other_registrar, Pubkey::new_rand()),
+        RegistryErrorCode::EntityRegistrarMismatch,
+    );
+}
+
+#[test]
+fn same_entity() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new(entity, false),
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    assert_error_code(
+        client.switch_entity_with_signers(&[&beneficiary, client.payer()], &accounts),
+        RegistryErrorCode::SameEntity,
+    );
+}
+
+#[test]
+fn member_entity_mismatch() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let other_leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, other_entity) = client
+        .create_entity_derived(&other_leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    // The member's current entity is given as the one to switch to.
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new(other_entity, false),
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    assert_error_code(
+        client.switch_entity_with_signers(&[&beneficiary, client.payer()], &accounts),
+        RegistryErrorCode::MemberEntityMismatch,
+    );
+}
+
+#[test]
+fn invalid_beneficiary() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    assert_error_code(
+        client.set_beneficiary_with_signers(
+            &[&beneficiary, client.payer()],
+            &accounts,
+            Pubkey::default(),
+        ),
+        RegistryErrorCode::InvalidBeneficiary,
+    );
+}
+
+#[test]
+fn not_initialized() {
+    let Fixture { client, leader, .. } = common::fixture();
+
+    // Owned by the program, but never initialized as an entity.
+    let uninitialized = create_program_account(&client, *entity::SIZE);
+    let accounts = [
+        AccountMeta::new(uninitialized, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+    ];
+    assert_error_code(
+        client.heartbeat_with_signers(&[&leader, client.payer()], &accounts),
+        RegistryErrorCode::NotInitialized,
+    );
+}
+
+#[test]
+fn leader_is_registrar_authority() {
+    let client = serum_common_tests::client::<Client>();
+    let registrar_authority = Keypair::generate(&mut OsRng);
+    let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
+    let (_tx_sig, registrar) = client
+        .create_account_and_initialize(&accounts, registrar_authority.pubkey(), 1234, true)
+        .unwrap();
+
+    assert_error_code(
+        client.create_entity_derived(
+            &registrar_authority,
+            registrar.pubkey(),
+            1,
+            StakeKind::Delegated,
+        ),
+        RegistryErrorCode::LeaderIsRegistrarAuthority,
+    );
+}
+
+#[test]
+fn invalid_clock_sysvar() {
+    let Fixture {
+        client,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+    ];
+    assert_error_code(
+        client.heartbeat_with_signers(&[&leader, client.payer()], &accounts),
+        RegistryErrorCode::InvalidClockSysvar,
+    );
+}
+
+#[test]
+fn blocklist_full() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    for _ in 0..MAX_BLOCKLIST {
+        client
+            .block_beneficiary_with_signers(
+                &[&registrar_authority, client.payer()],
+                &accounts,
+                Pubkey::new_rand(),
+            )
+            .unwrap();
+    }
+    assert_error_code(
+        client.block_beneficiary_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            Pubkey::new_rand(),
+        ),
+        RegistryErrorCode::BlocklistFull,
+    );
+}
+
+#[test]
+fn block_default_beneficiary() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    // Blocking the key marking empty slots would silently do nothing.
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert_error_code(
+        client.block_beneficiary_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            Pubkey::default(),
+        ),
+        RegistryErrorCode::InvalidBeneficiary,
+    );
+}
+
+#[test]
+fn invalid_metadata() {
+    let Fixture {
+        client,
+        leader,
+        entity,
+        ..
+    } = common::fixture();
+
+    // Anywhere but the address derived from the entity.
+    let accounts = [
+        AccountMeta::new(Pubkey::new_rand(), false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new(client.payer().pubkey(), true),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    assert_error_code(
+        client.create_metadata_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            "entity".to_string(),
+            String::new(),
+            String::new(),
+        ),
+        RegistryErrorCode::InvalidMetadata,
+    );
+}
+
+#[test]
+fn invalid_multisig() {
+    let client = serum_common_tests::client::<Client>();
+
+    assert_error_code(
+        client
+            .create_account_and_create_multisig(&[], 0, vec![Pubkey::new_rand()])
+            .map(|(tx_sig, _multisig)| tx_sig),
+        RegistryErrorCode::InvalidMultisig,
+    );
+}
+
+#[test]
+fn wrong_serialization() {
+    let client = serum_common_tests::client::<Client>();
+
+    let instr = Instruction {
+        program_id: *client.program(),
+        accounts: vec![],
+        data: vec![0xff; 4],
+    };
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instr],
+        Some(&client.payer().pubkey()),
+        &[client.payer()],
+        recent_hash,
+    );
+    assert_error_code(
+        client
+            .rpc()
+            .send_and_confirm_transaction_with_spinner_and_config(
+                &tx,
+                client.options().commitment,
+                client.options().tx,
+            )
+            .map_err(ClientError::RpcError),
+        RegistryErrorCode::WrongSerialization,
+    );
+}
+
+// Creates a zeroed account of the given size owned by the registry program.
+fn create_program_account(client: &Client, size: u64) -> Pubkey {
+    let kp = Keypair::generate(&mut OsRng);
+    let lamports = client
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(size as usize)
+        .unwrap();
+    let instr = system_instruction::create_account(
+        &client.payer().pubkey(),
+        &kp.pubkey(),
+        lamports,
+        size,
+        client.program(),
+    );
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instr],
+        Some(&client.payer().pubkey()),
+        &[client.payer(), &kp],
+        recent_hash,
+    );
+    client
+        .rpc()
+        .send_and_confirm_transaction_with_spinner_and_config(
+            &tx,
+            client.options().commitment,
+            client.options().tx,
+        )
+        .unwrap();
+    kp.pubkey()
+}
+
+fn assert_error_code<T: std::fmt::Debug>(result: Result<T, ClientError>, code: RegistryErrorCode) {
+    let err = result.unwrap_err();
+    assert_eq!(err.error_code(), Some(code.into()), "{}", err);
+}
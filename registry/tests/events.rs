@@ -0,0 +1,91 @@
+use rand::rngs::OsRng;
+use serum_registry::client::Client;
+use serum_registry::events::{self, RegistryEvent};
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::sysvar;
+
+#[test]
+fn events_are_logged() {
+    let client = serum_common_tests::client::<Client>();
+
+    let registrar_authority = Keypair::generate(&mut OsRng);
+    let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
+    let (_tx_sig, registrar) = client
+        .create_account_and_initialize(&accounts, registrar_authority.pubkey(), 1234, false)
+        .unwrap();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar.pubkey(), false),
+    ];
+    let instructions = [
+        serum_registry::instruction::register_capability(*client.program(), &accounts, 1, 1234),
+        serum_registry::instruction::pause(*client.program(), &accounts),
+    ];
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&client.payer().pubkey()),
+        &[client.payer(), &registrar_authority],
+        recent_hash,
+    );
+
+    let result = serum_common::client::rpc::simulate_transaction(
+        client.rpc(),
+        &tx,
+        true,
+        CommitmentConfig::single(),
+    )
+    .unwrap()
+    .value;
+    assert!(result.err.is_none());
+
+    assert_eq!(
+        events::decode_logs(client.program(), &result.logs.unwrap()),
+        vec![
+            RegistryEvent::CapabilityRegistered {
+                registrar: registrar.pubkey(),
+                capability_id: 1,
+                fee_bps: 1234,
+            },
+            RegistryEvent::RegistrarPaused {
+                registrar: registrar.pubkey(),
+                paused: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn simulate_reports_errors() {
+    let client = serum_common_tests::client::<Client>();
+
+    let registrar_authority = Keypair::generate(&mut OsRng);
+    let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
+    let (_tx_sig, registrar) = client
+        .create_account_and_initialize(&accounts, registrar_authority.pubkey(), 1234, false)
+        .unwrap();
+
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new(registrar.pubkey(), false),
+    ];
+    let instructions = [serum_registry::instruction::pause(
+        *client.program(),
+        &accounts,
+    )];
+
+    let simulation = client
+        .simulate(&[client.payer(), &imposter], &instructions)
+        .unwrap();
+    assert!(simulation.err.is_some());
+    assert!(simulation.events.is_empty());
+
+    // Nothing was committed.
+    let registrar: serum_registry::accounts::Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar.pubkey());
+    assert!(!registrar.paused);
+}
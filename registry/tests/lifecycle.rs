@@ -13,8 +13,9 @@ mod common;
 // NOTE: Deterministic derived addresses are used as a UX convenience so
 //       make sure tests are run against a new instance of the program.
 
-// lifecycle tests all instructions on the program in one go.
-// TODO: break this up into multiple tests.
+// lifecycle tests initializing a registrar, creating an entity, and joining
+// it in one go. Every other instruction is tested on its own, against
+// `common::fixture()`, in the file for the account it acts on.
 #[test]
 fn lifecycle() {
     // First test initiailze.
@@ -37,7 +38,12 @@ fn lifecycle() {
     let registrar_authority = Keypair::generate(&mut OsRng);
     let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
     let (_tx_sig, registrar) = client
-        .create_account_and_initialize(&accounts, registrar_authority.pubkey(), withdrawal_timelock)
+        .create_account_and_initialize(
+            &accounts,
+            registrar_authority.pubkey(),
+            withdrawal_timelock,
+            false,
+        )
         .unwrap();
 
     // Verify initialization.
@@ -47,26 +53,8 @@ fn lifecycle() {
         assert_eq!(registrar.initialized, true);
         assert_eq!(registrar.authority, registrar_authority.pubkey());
         assert_eq!(registrar.capabilities_fees_bps, [0; 32]);
-    }
-
-    // Register capabilities.
-    {
-        let capability_id = 1;
-        let capability_fee = 1234;
-        let accounts = [
-            AccountMeta::new_readonly(registrar_authority.pubkey(), true),
-            AccountMeta::new(registrar.pubkey(), false),
-        ];
-        let signers = [&registrar_authority, client.payer()];
-        client
-            .register_capability_with_signers(&signers, &accounts, capability_id, capability_fee)
-            .unwrap();
-
-        let registrar: Registrar =
-            serum_common::client::rpc::account_unpacked(client.rpc(), &registrar.pubkey());
-        let mut expected = [0; 32];
-        expected[capability_id as usize] = capability_fee;
-        assert_eq!(registrar.capabilities_fees_bps, expected);
+        assert_eq!(registrar.separate_leader_and_authority, false);
+        assert_eq!(registrar.pending_authority, Pubkey::default());
     }
 
     // Create entity.
@@ -77,17 +65,19 @@ fn lifecycle() {
         let stake_kind = StakeKind::Delegated;
 
         let (_tx_sig, entity_addr) = client
-            .create_entity_derived(&node_leader, capabilities, stake_kind)
+            .create_entity_derived(&node_leader, registrar.pubkey(), capabilities, stake_kind)
             .unwrap();
 
         let entity: Entity =
             serum_common::client::rpc::account_unpacked(client.rpc(), &entity_addr);
         assert_eq!(entity.leader, node_leader_pubkey);
         assert_eq!(entity.initialized, true);
+        assert_eq!(entity.registrar, registrar.pubkey());
         assert_eq!(entity.amount, 0);
         assert_eq!(entity.mega_amount, 0);
         assert_eq!(entity.capabilities, capabilities);
         assert_eq!(entity.stake_kind, stake_kind);
+        assert_eq!(entity.last_heartbeat_slot, 0);
 
         entity_addr
     };
@@ -97,6 +87,7 @@ fn lifecycle() {
         let accounts = [
             AccountMeta::new(entity, false),
             AccountMeta::new_readonly(node_leader.pubkey(), true),
+            AccountMeta::new_readonly(registrar.pubkey(), false),
         ];
 
         let new_capabilities = 1 | 2;
@@ -117,20 +108,35 @@ fn lifecycle() {
         assert_eq!(entity_account.leader, new_leader);
     }
 
-    // Join enitty.
+    // Join entity.
     let beneficiary = Keypair::generate(&mut OsRng);
     {
         let delegate = Pubkey::new_from_array([0; 32]);
+
+        // The registrar must be the one the entity was created with.
+        let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
+        let (_tx_sig, other_registrar) = client
+            .create_account_and_initialize(&accounts, registrar_authority.pubkey(), 1234, false)
+            .unwrap();
+        assert!(client
+            .join_entity_derived(
+                entity,
+                other_registrar.pubkey(),
+                beneficiary.pubkey(),
+                delegate
+            )
+            .is_err());
+
         let (_tx_sig, member_addr) = client
-            .join_entity_derived(entity, beneficiary.pubkey(), delegate)
+            .join_entity_derived(entity, registrar.pubkey(), beneficiary.pubkey(), delegate)
             .unwrap();
 
         let member: Member =
             serum_common::client::rpc::account_unpacked(client.rpc(), &member_addr);
         assert_eq!(member.initialized, true);
         assert_eq!(member.entity, entity);
-        assert_eq!(member.beneficiary, beneficiary.pubkey());
-        assert_eq!(member.delegate, Pubkey::new_from_array([0; 32]));
+        assert_eq!(member.authorities.beneficiary, beneficiary.pubkey());
+        assert_eq!(member.authorities.delegate(), None);
         assert_eq!(member.amount, 0);
         assert_eq!(member.mega_amount, 0);
     }
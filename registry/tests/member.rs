@@ -0,0 +1,214 @@
+use common::Fixture;
+use rand::rngs::OsRng;
+use serum_registry::accounts::{Entity, Member, StakeKind};
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+
+mod common;
+
+#[test]
+fn set_and_revoke_delegate() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let delegate = Pubkey::new_rand();
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(client.payer().pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    assert!(client.set_delegate(&accounts, delegate).is_err());
+
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    client
+        .set_delegate_with_signers(&[&beneficiary, client.payer()], &accounts, delegate)
+        .unwrap();
+    let member_account: Member = serum_common::client::rpc::account_unpacked(client.rpc(), &member);
+    assert_eq!(member_account.authorities.delegate(), Some(&delegate));
+
+    client
+        .revoke_delegate_with_signers(&[&beneficiary, client.payer()], &accounts)
+        .unwrap();
+    let member_account: Member = serum_common::client::rpc::account_unpacked(client.rpc(), &member);
+    assert_eq!(member_account.authorities.delegate(), None);
+}
+
+#[test]
+fn switch_entity() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let other_leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, other_entity) = client
+        .create_entity_derived(&other_leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new(entity, false),
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    assert!(client
+        .switch_entity_with_signers(&[&beneficiary, client.payer()], &accounts)
+        .is_err());
+
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new(entity, false),
+        AccountMeta::new(other_entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    client
+        .switch_entity_with_signers(&[&beneficiary, client.payer()], &accounts)
+        .unwrap();
+    let member_account: Member = serum_common::client::rpc::account_unpacked(client.rpc(), &member);
+    assert_eq!(member_account.entity, other_entity);
+    let entity_account: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity_account.members, 0);
+    let other_entity_account: Entity =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &other_entity);
+    assert_eq!(other_entity_account.members, 1);
+
+    // The member no longer belongs to the old entity.
+    assert!(client
+        .switch_entity_with_signers(&[&beneficiary, client.payer()], &accounts)
+        .is_err());
+}
+
+#[test]
+fn set_beneficiary() {
+    let Fixture {
+        client,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    assert!(client
+        .set_beneficiary_confirmed(&member, &beneficiary, Pubkey::default())
+        .is_err());
+
+    let new_beneficiary = Keypair::generate(&mut OsRng);
+    let (_tx_sig, member_account) = client
+        .set_beneficiary_confirmed(&member, &beneficiary, new_beneficiary.pubkey())
+        .unwrap();
+    assert_eq!(
+        member_account.authorities.beneficiary,
+        new_beneficiary.pubkey()
+    );
+
+    // The old beneficiary is locked out.
+    assert!(client
+        .set_beneficiary_confirmed(&member, &beneficiary, beneficiary.pubkey())
+        .is_err());
+
+    client
+        .set_beneficiary_confirmed(&member, &new_beneficiary, beneficiary.pubkey())
+        .unwrap();
+}
+
+#[test]
+fn membership_changes_fail_while_paused() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .pause_with_signers(&[&registrar_authority, client.payer()], &accounts)
+        .unwrap();
+
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new_readonly(entity, false),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    assert!(client
+        .set_delegate_with_signers(
+            &[&beneficiary, client.payer()],
+            &accounts,
+            Pubkey::new_rand()
+        )
+        .is_err());
+    assert!(client
+        .revoke_delegate_with_signers(&[&beneficiary, client.payer()], &accounts)
+        .is_err());
+    assert!(client
+        .set_beneficiary_with_signers(
+            &[&beneficiary, client.payer()],
+            &accounts,
+            Pubkey::new_rand()
+        )
+        .is_err());
+}
+
+#[test]
+fn close_member() {
+    let Fixture {
+        client,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    let destination = Pubkey::new_rand();
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(client.payer().pubkey(), true),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(entity, false),
+    ];
+    assert!(client.close_member(&accounts).is_err());
+
+    let member_lamports = client.rpc().get_balance(&member).unwrap();
+    let accounts = [
+        AccountMeta::new(member, false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), true),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(entity, false),
+    ];
+    client
+        .close_member_with_signers(&[&beneficiary, client.payer()], &accounts)
+        .unwrap();
+
+    assert!(client.rpc().get_account(&member).is_err());
+    assert_eq!(
+        client.rpc().get_balance(&destination).unwrap(),
+        member_lamports
+    );
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity.members, 0);
+}
@@ -0,0 +1,39 @@
+use common::Fixture;
+use serum_common::pack::{layout_version, Versioned};
+use serum_registry::accounts::{Entity, Member, Registrar};
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+
+mod common;
+
+#[test]
+fn migrate_account() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        member,
+        ..
+    } = common::fixture();
+
+    // Current accounts migrate to themselves.
+    let versions = [
+        (registrar, Registrar::VERSION),
+        (entity, Entity::VERSION),
+        (member, Member::VERSION),
+    ];
+    for (address, version) in versions.iter() {
+        let before = client.rpc().get_account_data(address).unwrap();
+        client
+            .migrate_account(&[AccountMeta::new(*address, false)])
+            .unwrap();
+        let after = client.rpc().get_account_data(address).unwrap();
+        assert_eq!(layout_version(&after).unwrap(), *version);
+        assert_eq!(before, after);
+    }
+
+    // Only program owned accounts can be migrated.
+    assert!(client
+        .migrate_account(&[AccountMeta::new(client.payer().pubkey(), false)])
+        .is_err());
+}
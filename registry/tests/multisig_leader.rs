@@ -0,0 +1,146 @@
+use rand::rngs::OsRng;
+use serum_registry::accounts::{Entity, Multisig, StakeKind};
+use serum_registry::client::Client;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::sysvar;
+
+mod common;
+
+#[test]
+fn multisig_leader_updates_entity() {
+    let client = serum_common_tests::client::<Client>();
+    let (_authority, registrar) = common::registrar(&client);
+
+    // 2-of-3 multisig.
+    let a = Keypair::generate(&mut OsRng);
+    let b = Keypair::generate(&mut OsRng);
+    let c = Keypair::generate(&mut OsRng);
+    assert!(client
+        .create_account_and_create_multisig(&[], 0, vec![a.pubkey(), b.pubkey()])
+        .is_err());
+    let (_tx_sig, multisig) = client
+        .create_account_and_create_multisig(&[], 2, vec![a.pubkey(), b.pubkey(), c.pubkey()])
+        .unwrap();
+    let multisig_account: Multisig =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &multisig.pubkey());
+    assert_eq!(multisig_account.m, 2);
+    assert_eq!(
+        multisig_account.signers(),
+        &[a.pubkey(), b.pubkey(), c.pubkey()]
+    );
+
+    // Hand the entity over to the multisig.
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    client
+        .update_entity_with_signers(&[&leader, client.payer()], &accounts, multisig.pubkey(), 1)
+        .unwrap();
+
+    // The old leader no longer has control.
+    assert!(client
+        .update_entity_with_signers(&[&leader, client.payer()], &accounts, leader.pubkey(), 1)
+        .is_err());
+
+    // Below the threshold.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(multisig.pubkey(), false),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(a.pubkey(), true),
+    ];
+    assert!(client
+        .update_entity_with_signers(&[&a, client.payer()], &accounts, multisig.pubkey(), 3)
+        .is_err());
+
+    // At the threshold.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(multisig.pubkey(), false),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(a.pubkey(), true),
+        AccountMeta::new_readonly(c.pubkey(), true),
+    ];
+    client
+        .update_entity_with_signers(&[&a, &c, client.payer()], &accounts, multisig.pubkey(), 3)
+        .unwrap();
+
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert_eq!(entity.leader, multisig.pubkey());
+    assert_eq!(entity.capabilities, 3);
+}
+
+#[test]
+fn multisig_leader_heartbeats_and_sets_capabilities() {
+    let client = serum_common_tests::client::<Client>();
+    let (_authority, registrar) = common::registrar(&client);
+
+    // 2-of-2 multisig.
+    let a = Keypair::generate(&mut OsRng);
+    let b = Keypair::generate(&mut OsRng);
+    let (_tx_sig, multisig) = client
+        .create_account_and_create_multisig(&[], 2, vec![a.pubkey(), b.pubkey()])
+        .unwrap();
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity) = client
+        .create_entity_derived(&leader, registrar, 0, StakeKind::Delegated)
+        .unwrap();
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    client
+        .update_entity_with_signers(&[&leader, client.payer()], &accounts, multisig.pubkey(), 0)
+        .unwrap();
+
+    // Below the threshold.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(multisig.pubkey(), false),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+        AccountMeta::new_readonly(a.pubkey(), true),
+    ];
+    assert!(client
+        .heartbeat_with_signers(&[&a, client.payer()], &accounts)
+        .is_err());
+
+    // At the threshold.
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(multisig.pubkey(), false),
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+        AccountMeta::new_readonly(a.pubkey(), true),
+        AccountMeta::new_readonly(b.pubkey(), true),
+    ];
+    client
+        .heartbeat_with_signers(&[&a, &b, client.payer()], &accounts)
+        .unwrap();
+
+    let accounts = [
+        AccountMeta::new(entity, false),
+        AccountMeta::new_readonly(multisig.pubkey(), false),
+        AccountMeta::new_readonly(registrar, false),
+        AccountMeta::new_readonly(a.pubkey(), true),
+        AccountMeta::new_readonly(b.pubkey(), true),
+    ];
+    client
+        .add_capabilities_with_signers(&[&a, &b, client.payer()], &accounts, 2 | 4)
+        .unwrap();
+    client
+        .remove_capabilities_with_signers(&[&a, &b, client.payer()], &accounts, 2)
+        .unwrap();
+
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity);
+    assert!(entity.last_heartbeat_slot > 0);
+    assert_eq!(entity.capabilities, 4);
+}
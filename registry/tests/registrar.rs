@@ -0,0 +1,298 @@
+use common::Fixture;
+use rand::rngs::OsRng;
+use serum_registry::accounts::{Registrar, StakeKind};
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+
+mod common;
+
+#[test]
+fn register_capability() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let capability_id = 1;
+    let capability_fee = 1234;
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    let signers = [&registrar_authority, client.payer()];
+    client
+        .register_capability_with_signers(&signers, &accounts, capability_id, capability_fee)
+        .unwrap();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    let mut expected = [0; 32];
+    expected[capability_id as usize] = capability_fee;
+    assert_eq!(registrar_account.capabilities_fees_bps, expected);
+
+    // Ids past the last slot are rejected on chain.
+    assert!(client
+        .register_capability_with_signers(&signers, &accounts, 32, capability_fee)
+        .is_err());
+}
+
+#[test]
+fn set_fee_schedule() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let schedule = [(0, 10), (2, 20), (3, 30), (17, 40), (31, 50)];
+    client
+        .set_fee_schedule(&registrar, &registrar_authority, &schedule)
+        .unwrap();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    let mut expected = [0; 32];
+    for (capability_id, fee_bps) in schedule.iter() {
+        expected[*capability_id as usize] = *fee_bps;
+    }
+    assert_eq!(registrar_account.capabilities_fees_bps, expected);
+
+    // Invalid schedules are rejected before sending.
+    let invalid: [&[(u8, u32)]; 3] = [&[(32, 1)], &[(1, 10_001)], &[(1, 1), (1, 2)]];
+    for schedule in invalid.iter() {
+        assert!(client
+            .set_fee_schedule(&registrar, &registrar_authority, schedule)
+            .is_err());
+    }
+}
+
+#[test]
+fn estimate_fee() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    let instr =
+        serum_registry::instruction::register_capability(*client.program(), &accounts, 1, 1);
+    let fee = client.estimate_fee(&[instr]).unwrap();
+
+    // One signature for the payer and one for the registrar authority.
+    let (_recent_hash, fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    assert_eq!(fee, 2 * fee_calc.lamports_per_signature);
+}
+
+#[test]
+fn block_and_unblock_beneficiary() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        entity,
+        ..
+    } = common::fixture();
+
+    let blocked = Pubkey::new_rand();
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .block_beneficiary_with_signers(&[&registrar_authority, client.payer()], &accounts, blocked)
+        .unwrap();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert!(registrar_account.is_blocked(&blocked));
+    assert!(common::try_join_entity(&client, entity, registrar, blocked).is_err());
+
+    client
+        .unblock_beneficiary_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            blocked,
+        )
+        .unwrap();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert!(!registrar_account.is_blocked(&blocked));
+    common::join_entity(&client, entity, registrar, blocked);
+}
+
+#[test]
+fn pause_and_unpause() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        entity,
+        ..
+    } = common::fixture();
+
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert!(client
+        .pause_with_signers(&[&imposter, client.payer()], &accounts)
+        .is_err());
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .pause_with_signers(&[&registrar_authority, client.payer()], &accounts)
+        .unwrap();
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert!(registrar_account.paused);
+
+    let leader = Keypair::generate(&mut OsRng);
+    assert!(client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .is_err());
+    assert!(common::try_join_entity(&client, entity, registrar, Pubkey::new_rand()).is_err());
+
+    client
+        .unpause_with_signers(&[&registrar_authority, client.payer()], &accounts)
+        .unwrap();
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert!(!registrar_account.paused);
+    common::join_entity(&client, entity, registrar, Pubkey::new_rand());
+}
+
+#[test]
+fn update_registrar() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    let withdrawal_timelock = registrar_account.withdrawal_timelock;
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .update_registrar_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            Some(withdrawal_timelock + 1),
+            None,
+            None,
+        )
+        .unwrap();
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert_eq!(
+        registrar_account.withdrawal_timelock,
+        withdrawal_timelock + 1
+    );
+    assert_eq!(registrar_account.separate_leader_and_authority, false);
+
+    client
+        .update_registrar_with_signers(
+            &[&registrar_authority, client.payer()],
+            &accounts,
+            None,
+            Some(true),
+            None,
+        )
+        .unwrap();
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert_eq!(
+        registrar_account.withdrawal_timelock,
+        withdrawal_timelock + 1
+    );
+    assert_eq!(registrar_account.separate_leader_and_authority, true);
+
+    // Only the authority can update.
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert!(client
+        .update_registrar_with_signers(&[&imposter, client.payer()], &accounts, Some(0), None, None)
+        .is_err());
+}
+
+#[test]
+fn transfer_authority() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        ..
+    } = common::fixture();
+
+    let governance_accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    let new_authority = Keypair::generate(&mut OsRng);
+    client
+        .set_pending_authority_with_signers(
+            &[&registrar_authority, client.payer()],
+            &governance_accounts,
+            new_authority.pubkey(),
+        )
+        .unwrap();
+
+    // Control doesn't change hands until the new authority accepts.
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert_eq!(registrar_account.authority, registrar_authority.pubkey());
+    assert_eq!(registrar_account.pending_authority, new_authority.pubkey());
+
+    let imposter = Keypair::generate(&mut OsRng);
+    let accounts = [
+        AccountMeta::new_readonly(imposter.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    assert!(client
+        .accept_authority_with_signers(&[&imposter, client.payer()], &accounts)
+        .is_err());
+
+    let accounts = [
+        AccountMeta::new_readonly(new_authority.pubkey(), true),
+        AccountMeta::new(registrar, false),
+    ];
+    client
+        .accept_authority_with_signers(&[&new_authority, client.payer()], &accounts)
+        .unwrap();
+
+    let registrar_account: Registrar =
+        serum_common::client::rpc::account_unpacked(client.rpc(), &registrar);
+    assert_eq!(registrar_account.authority, new_authority.pubkey());
+    assert_eq!(registrar_account.pending_authority, Pubkey::default());
+
+    // The old authority is locked out.
+    assert!(client
+        .set_pending_authority_with_signers(
+            &[&registrar_authority, client.payer()],
+            &governance_accounts,
+            registrar_authority.pubkey(),
+        )
+        .is_err());
+}
@@ -0,0 +1,124 @@
+use rand::rngs::OsRng;
+use serum_registry::accounts::{Entity, StakeKind};
+use serum_registry::client::Client;
+use serum_registry::error::RegistryErrorCode;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+
+mod common;
+
+#[test]
+fn authority_can_lead_entity_by_default() {
+    let client = serum_common_tests::client::<Client>();
+    let (registrar_authority, registrar) =
+        common::registrar_with_separate_leader_and_authority(&client, false);
+
+    let (_tx_sig, entity_addr) = client
+        .create_entity_derived(&registrar_authority, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity_addr);
+    assert_eq!(entity.leader, registrar_authority.pubkey());
+}
+
+#[test]
+fn authority_cannot_lead_entity_when_separated() {
+    let client = serum_common_tests::client::<Client>();
+    let (registrar_authority, registrar) =
+        common::registrar_with_separate_leader_and_authority(&client, true);
+
+    assert!(client
+        .create_entity_derived(&registrar_authority, registrar, 1, StakeKind::Delegated)
+        .is_err());
+
+    // Any other leader is still allowed.
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity_addr) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity_addr);
+    assert_eq!(entity.leader, leader.pubkey());
+}
+
+#[test]
+fn entity_cannot_be_handed_to_authority_when_separated() {
+    let client = serum_common_tests::client::<Client>();
+    let (registrar_authority, registrar) =
+        common::registrar_with_separate_leader_and_authority(&client, true);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity_addr) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+    let accounts = [
+        AccountMeta::new(entity_addr, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    let err = client
+        .update_entity_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            registrar_authority.pubkey(),
+            1,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::LeaderIsRegistrarAuthority.into())
+    );
+
+    // The policy is read from the entity's own registrar.
+    let (_other_authority, other_registrar) =
+        common::registrar_with_separate_leader_and_authority(&client, false);
+    let accounts = [
+        AccountMeta::new(entity_addr, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(other_registrar, false),
+    ];
+    let err = client
+        .update_entity_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            registrar_authority.pubkey(),
+            1,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.error_code(),
+        Some(RegistryErrorCode::EntityRegistrarMismatch.into())
+    );
+
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity_addr);
+    assert_eq!(entity.leader, leader.pubkey());
+}
+
+#[test]
+fn entity_can_be_handed_to_authority_by_default() {
+    let client = serum_common_tests::client::<Client>();
+    let (registrar_authority, registrar) =
+        common::registrar_with_separate_leader_and_authority(&client, false);
+
+    let leader = Keypair::generate(&mut OsRng);
+    let (_tx_sig, entity_addr) = client
+        .create_entity_derived(&leader, registrar, 1, StakeKind::Delegated)
+        .unwrap();
+    let accounts = [
+        AccountMeta::new(entity_addr, false),
+        AccountMeta::new_readonly(leader.pubkey(), true),
+        AccountMeta::new_readonly(registrar, false),
+    ];
+    client
+        .update_entity_with_signers(
+            &[&leader, client.payer()],
+            &accounts,
+            registrar_authority.pubkey(),
+            1,
+        )
+        .unwrap();
+
+    let entity: Entity = serum_common::client::rpc::account_unpacked(client.rpc(), &entity_addr);
+    assert_eq!(entity.leader, registrar_authority.pubkey());
+}
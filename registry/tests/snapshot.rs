@@ -0,0 +1,106 @@
+use common::Fixture;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::signature::Signer;
+
+mod common;
+
+#[test]
+fn members() {
+    let Fixture {
+        client,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    // Batch fetch, preserving order, with missing accounts as None.
+    let missing = Pubkey::new_rand();
+    let members = client.members(&[missing, member, missing]).unwrap();
+    assert_eq!(members.len(), 3);
+    assert!(members[0].is_none());
+    assert_eq!(
+        members[1].as_ref().unwrap().authorities.beneficiary,
+        beneficiary.pubkey()
+    );
+    assert!(members[2].is_none());
+}
+
+#[test]
+fn full_snapshot() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        entity,
+        member,
+        ..
+    } = common::fixture();
+
+    let snapshot = client.full_snapshot(&registrar).unwrap();
+    assert_eq!(snapshot.registrar.authority, registrar_authority.pubkey());
+    assert_eq!(snapshot.entities.len(), 1);
+    assert_eq!(snapshot.entities[0].0, entity);
+    assert_eq!(snapshot.members.len(), 1);
+    assert_eq!(snapshot.members[0].0, member);
+    assert_eq!(snapshot.members[0].1.entity, entity);
+
+    // Entities of other registrars aren't included.
+    let (_authority, other_registrar) = common::registrar(&client);
+    let snapshot = client.full_snapshot(&other_registrar).unwrap();
+    assert!(snapshot.entities.is_empty());
+    assert!(snapshot.members.is_empty());
+}
+
+#[test]
+fn entities_and_members() {
+    let Fixture {
+        client,
+        registrar,
+        entity,
+        member,
+        ..
+    } = common::fixture();
+
+    let entities = client.entities(&registrar).unwrap();
+    assert_eq!(entities.len(), 1);
+    assert_eq!(entities[0].0, entity);
+    let (_authority, other_registrar) = common::registrar(&client);
+    assert!(client.entities(&other_registrar).unwrap().is_empty());
+
+    let members = client.entity_members(&entity).unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].0, member);
+    assert!(client
+        .entity_members(&Pubkey::new_rand())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn member_snapshot() {
+    let Fixture {
+        client,
+        registrar_authority,
+        registrar,
+        entity,
+        beneficiary,
+        member,
+        ..
+    } = common::fixture();
+
+    // A single member, in one request.
+    let snapshot = client
+        .member_snapshot(&registrar, &entity, &member)
+        .unwrap();
+    assert_eq!(snapshot.registrar.authority, registrar_authority.pubkey());
+    assert_eq!(snapshot.entity.registrar, registrar);
+    assert_eq!(
+        snapshot.member.authorities.beneficiary,
+        beneficiary.pubkey()
+    );
+
+    let (_authority, other_registrar) = common::registrar(&client);
+    assert!(client
+        .member_snapshot(&other_registrar, &entity, &member)
+        .is_err());
+}
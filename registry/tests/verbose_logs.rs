@@ -0,0 +1,53 @@
+#![cfg(feature = "verbose-logs")]
+
+use rand::rngs::OsRng;
+use serum_registry::client::Client;
+use solana_client_gen::prelude::*;
+use solana_client_gen::solana_sdk::instruction::AccountMeta;
+use solana_client_gen::solana_sdk::signature::{Keypair, Signer};
+use solana_client_gen::solana_sdk::sysvar;
+
+// Requires the program to be deployed with the `verbose-logs` feature.
+#[test]
+fn verbose_logs() {
+    let client = serum_common_tests::client::<Client>();
+
+    let registrar_authority = Keypair::generate(&mut OsRng);
+    let accounts = [AccountMeta::new_readonly(sysvar::rent::ID, false)];
+    let (_tx_sig, registrar) = client
+        .create_account_and_initialize(&accounts, registrar_authority.pubkey(), 1234, false)
+        .unwrap();
+
+    let accounts = [
+        AccountMeta::new_readonly(registrar_authority.pubkey(), true),
+        AccountMeta::new(registrar.pubkey(), false),
+    ];
+    let instr =
+        serum_registry::instruction::register_capability(*client.program(), &accounts, 1, 1234);
+    let (recent_hash, _fee_calc) = client.rpc().get_recent_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instr],
+        Some(&client.payer().pubkey()),
+        &[client.payer(), &registrar_authority],
+        recent_hash,
+    );
+
+    let result = serum_common::client::rpc::simulate_transaction(
+        client.rpc(),
+        &tx,
+        true,
+        CommitmentConfig::single(),
+    )
+    .unwrap()
+    .value;
+    assert!(result.err.is_none());
+
+    let logs = result.logs.unwrap();
+    for expected in &[
+        "handler: register_capability",
+        "access-control: register_capability",
+        "state-transition: register_capability",
+    ] {
+        assert!(logs.iter().any(|l| l.contains(expected)));
+    }
+}